@@ -0,0 +1,294 @@
+//! Command-line arguments for setting the initial configuration.
+//!
+//! Any setting that isn't given keeps its value from the previous launch.
+
+use bevy::{math::DVec3, prelude::*, window::PresentMode};
+use clap::{Parser, ValueEnum};
+
+use crate::{
+    configuration::{ActiveSimulation, ActiveTimesteps, Timestep},
+    interpolation::InterpolationMode,
+    settings::Settings,
+    simulation::{
+        DivergenceReference, MAX_BETA, MAX_BOID_COUNT, MAX_EMISSION_RATE, MAX_EVENT_RATE,
+        MAX_FIRE_RATE, MAX_RHO, MAX_SIGMA, MAX_START, MAX_STIFFNESS, MAX_TRAIL_LENGTH,
+        MAX_WHEEL_SPEED, MIN_BETA, MIN_BOID_COUNT, MIN_EMISSION_RATE, MIN_EVENT_RATE,
+        MIN_FIRE_RATE, MIN_RHO, MIN_SIGMA, MIN_STIFFNESS, MIN_TRAIL_LENGTH, MIN_WHEEL_SPEED,
+    },
+    ui::{
+        FullscreenMode, MAX_MAX_DELTA_MS, MAX_TIME_SCALE, MIN_TIME_SCALE, SLIDER_PRECISION,
+        UpdateModeVariant,
+    },
+};
+
+#[derive(Parser)]
+#[command(version, about)]
+pub struct Args {
+    /// The simulation to show
+    #[arg(long)]
+    simulation: Option<ActiveSimulation>,
+    /// Comma-separated list of the timesteps to run
+    #[arg(long, value_delimiter = ',')]
+    timesteps: Option<Vec<Timestep>>,
+    /// Simulation rate in Hz (updates per second)
+    #[arg(long, value_parser = parse_in_range(1.0, SLIDER_PRECISION))]
+    sim_rate: Option<f32>,
+    /// Relative speed of virtual time, for slow motion
+    #[arg(long, value_parser = parse_in_range(MIN_TIME_SCALE, MAX_TIME_SCALE))]
+    time_scale: Option<f32>,
+    /// Largest frame delta in milliseconds that virtual time will accept, or "unclamped"
+    #[arg(long, value_parser = parse_max_delta)]
+    max_delta: Option<MaxDeltaArg>,
+    /// How the Fixed timestep is rendered
+    #[arg(long)]
+    interpolation: Option<InterpolationMode>,
+    /// Whether each key press is handed to exactly one update of each timestep
+    #[arg(long)]
+    input_buffering: Option<bool>,
+    /// σ parameter of the Lorenz Attractor simulation
+    #[arg(long, value_parser = parse_in_range(MIN_SIGMA, MAX_SIGMA))]
+    lorenz_sigma: Option<f32>,
+    /// ρ parameter of the Lorenz Attractor simulation
+    #[arg(long, value_parser = parse_in_range(MIN_RHO, MAX_RHO))]
+    lorenz_rho: Option<f32>,
+    /// β parameter of the Lorenz Attractor simulation
+    #[arg(long, value_parser = parse_in_range(MIN_BETA, MAX_BETA))]
+    lorenz_beta: Option<f32>,
+    /// Starting point of the Lorenz Attractor simulation's trajectories, as "x,y,z"
+    #[arg(long, value_parser = parse_lorenz_start)]
+    lorenz_start: Option<DVec3>,
+    /// Whether each timestep's trajectory starts slightly apart from the others
+    #[arg(long)]
+    lorenz_offset_starts: Option<bool>,
+    /// What the Lorenz Attractor simulation measures divergence against: "high-rate", or a timestep
+    #[arg(long, value_parser = parse_divergence_reference)]
+    lorenz_reference: Option<DivergenceReference>,
+    /// Line segments kept in each of the Lorenz Attractor simulation's trails
+    #[arg(long, value_parser = clap::value_parser!(u32).range((MIN_TRAIL_LENGTH as i64)..=(MAX_TRAIL_LENGTH as i64)))]
+    lorenz_trail_length: Option<u32>,
+    /// Spring constant of the Spring Chain simulation
+    #[arg(long, value_parser = parse_in_range(MIN_STIFFNESS, MAX_STIFFNESS))]
+    spring_stiffness: Option<f32>,
+    /// Revolutions per second of the Wheels simulation
+    #[arg(long, value_parser = parse_in_range(MIN_WHEEL_SPEED, MAX_WHEEL_SPEED))]
+    wheel_speed: Option<f32>,
+    /// Particles per second of each fountain in the Fountain simulation
+    #[arg(long, value_parser = parse_in_range(MIN_EMISSION_RATE, MAX_EMISSION_RATE))]
+    emission_rate: Option<f32>,
+    /// Shots per second of the Rapid Fire simulation
+    #[arg(long, value_parser = parse_in_range(MIN_FIRE_RATE, MAX_FIRE_RATE))]
+    fire_rate: Option<f32>,
+    /// Whether the Rapid Fire simulation fires once for every time its timer finished during an update
+    #[arg(long)]
+    accurate_fire: Option<bool>,
+    /// Average events per second of the Stochastic simulation
+    #[arg(long, value_parser = parse_in_range(MIN_EVENT_RATE, MAX_EVENT_RATE))]
+    event_rate: Option<f32>,
+    /// Seed for the Stochastic simulation's random number generators. Only repeats the same events with the No Delta and Fixed timesteps
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Boids per flock in the Boids simulation
+    #[arg(long, value_parser = clap::value_parser!(u32).range((MIN_BOID_COUNT as i64)..=(MAX_BOID_COUNT as i64)))]
+    boid_count: Option<u32>,
+    /// The timestep that moves the Camera Follow simulation's target
+    #[arg(long)]
+    camera_follow_target: Option<Timestep>,
+    /// The timestep that moves the Camera Follow simulation's camera
+    #[arg(long)]
+    camera_follow_camera: Option<Timestep>,
+    /// How many frames to wait between each lag frame
+    #[arg(long, value_parser = clap::value_parser!(u32).range(0..=(SLIDER_PRECISION as i64)))]
+    lag_frequency: Option<u32>,
+    /// Extra time in milliseconds to wait for, on each lag frame
+    #[arg(long, value_parser = clap::value_parser!(u64).range(0..=(SLIDER_PRECISION as u64)))]
+    lag_duration: Option<u64>,
+    /// The window presentation mode
+    #[arg(long)]
+    present_mode: Option<PresentModeArg>,
+    /// The window fullscreen mode
+    #[arg(long)]
+    window_mode: Option<FullscreenMode>,
+    /// How the app updates while the window is in focus
+    #[arg(long)]
+    focused_update_mode: Option<UpdateModeVariant>,
+    /// How the app updates while the window is unfocused
+    #[arg(long)]
+    unfocused_update_mode: Option<UpdateModeVariant>,
+}
+
+/// Mirror of [`PresentMode`], which can't implement [`ValueEnum`] itself
+#[derive(ValueEnum, Clone, Copy)]
+enum PresentModeArg {
+    AutoVsync,
+    AutoNoVsync,
+    Fifo,
+    FifoRelaxed,
+    Immediate,
+    Mailbox,
+}
+
+impl From<PresentModeArg> for PresentMode {
+    fn from(mode: PresentModeArg) -> Self {
+        match mode {
+            PresentModeArg::AutoVsync => Self::AutoVsync,
+            PresentModeArg::AutoNoVsync => Self::AutoNoVsync,
+            PresentModeArg::Fifo => Self::Fifo,
+            PresentModeArg::FifoRelaxed => Self::FifoRelaxed,
+            PresentModeArg::Immediate => Self::Immediate,
+            PresentModeArg::Mailbox => Self::Mailbox,
+        }
+    }
+}
+
+fn parse_in_range(min: f32, max: f32) -> impl Fn(&str) -> Result<f32, String> + Clone {
+    move |arg| {
+        let value: f32 = arg.parse().map_err(|error| format!("{error}"))?;
+        if (min..=max).contains(&value) {
+            Ok(value)
+        } else {
+            Err(format!("must be between {min} and {max}"))
+        }
+    }
+}
+
+/// Milliseconds, or `None` for unclamped
+#[derive(Clone, Copy)]
+struct MaxDeltaArg(Option<f32>);
+
+fn parse_max_delta(arg: &str) -> Result<MaxDeltaArg, String> {
+    if arg == "unclamped" {
+        return Ok(MaxDeltaArg(None));
+    }
+
+    parse_in_range(1.0, MAX_MAX_DELTA_MS)(arg)
+        .map(|max_delta| MaxDeltaArg(Some(max_delta)))
+        .map_err(|error| format!("{error} (or \"unclamped\")"))
+}
+
+fn parse_lorenz_start(arg: &str) -> Result<DVec3, String> {
+    let coordinates = arg
+        .split(',')
+        .map(parse_in_range(-MAX_START, MAX_START))
+        .collect::<Result<Vec<_>, _>>()?;
+    match coordinates[..] {
+        [x, y, z] => Ok(Vec3::new(x, y, z).as_dvec3()),
+        _ => Err("must be three comma-separated coordinates".to_string()),
+    }
+}
+
+fn parse_divergence_reference(arg: &str) -> Result<DivergenceReference, String> {
+    if arg == "high-rate" {
+        return Ok(DivergenceReference::HighRate);
+    }
+
+    Timestep::from_str(arg, false)
+        .map(DivergenceReference::Timestep)
+        .map_err(|error| format!("{error} (or \"high-rate\")"))
+}
+
+/// Overwrites the configuration with the values given on the command line.
+///
+/// Must be added after [`settings::plugin`](crate::settings::plugin), so as to take priority over the previous launch.
+impl Plugin for Args {
+    fn build(&self, app: &mut App) {
+        let world = app.world_mut();
+        let mut settings = Settings::capture(world);
+
+        if let Some(simulation) = self.simulation {
+            settings.simulation = simulation;
+        }
+        if let Some(timesteps) = &self.timesteps {
+            settings.timesteps = timesteps
+                .iter()
+                .copied()
+                .map(ActiveTimesteps::from)
+                .fold(ActiveTimesteps::empty(), ActiveTimesteps::union);
+        }
+        if let Some(rate) = self.sim_rate {
+            settings.simulation_rate = rate;
+        }
+        if let Some(time_scale) = self.time_scale {
+            settings.time_scale = time_scale;
+        }
+        if let Some(MaxDeltaArg(max_delta_ms)) = self.max_delta {
+            settings.max_delta_ms = max_delta_ms;
+        }
+        if let Some(interpolation) = self.interpolation {
+            settings.interpolation = interpolation;
+        }
+        if let Some(input_buffering) = self.input_buffering {
+            settings.input_buffering = input_buffering;
+        }
+        if let Some(sigma) = self.lorenz_sigma {
+            settings.lorenz.sigma = sigma as f64;
+        }
+        if let Some(rho) = self.lorenz_rho {
+            settings.lorenz.rho = rho as f64;
+        }
+        if let Some(beta) = self.lorenz_beta {
+            settings.lorenz.beta = beta as f64;
+        }
+        if let Some(start) = self.lorenz_start {
+            settings.lorenz.start = start;
+        }
+        if let Some(offset_starts) = self.lorenz_offset_starts {
+            settings.lorenz.offset_starts = offset_starts;
+        }
+        if let Some(reference) = self.lorenz_reference {
+            settings.lorenz.reference = reference;
+        }
+        if let Some(length) = self.lorenz_trail_length {
+            settings.lorenz.trail_length = length;
+        }
+        if let Some(stiffness) = self.spring_stiffness {
+            settings.spring_stiffness = stiffness;
+        }
+        if let Some(speed) = self.wheel_speed {
+            settings.wheel_speed = speed;
+        }
+        if let Some(rate) = self.emission_rate {
+            settings.emission_rate = rate;
+        }
+        if let Some(rate) = self.fire_rate {
+            settings.rapid_fire.rate = rate;
+        }
+        if let Some(accurate) = self.accurate_fire {
+            settings.rapid_fire.accurate = accurate;
+        }
+        if let Some(rate) = self.event_rate {
+            settings.stochastic.rate = rate;
+        }
+        if let Some(seed) = self.seed {
+            settings.stochastic.seed = seed;
+        }
+        if let Some(count) = self.boid_count {
+            settings.boid_count = count;
+        }
+        if let Some(timestep) = self.camera_follow_target {
+            settings.camera_follow.target = timestep;
+        }
+        if let Some(timestep) = self.camera_follow_camera {
+            settings.camera_follow.camera = timestep;
+        }
+        if let Some(frames_delay) = self.lag_frequency {
+            settings.lag.frames_delay = frames_delay;
+        }
+        if let Some(lag_duration_ms) = self.lag_duration {
+            settings.lag.lag_duration_ms = lag_duration_ms;
+        }
+        if let Some(present_mode) = self.present_mode {
+            settings.present_mode = present_mode.into();
+        }
+        if let Some(window_mode) = self.window_mode {
+            settings.window_mode = window_mode;
+        }
+        if let Some(variant) = self.focused_update_mode {
+            settings.update_modes.focused.variant = variant;
+        }
+        if let Some(variant) = self.unfocused_update_mode {
+            settings.update_modes.unfocused.variant = variant;
+        }
+
+        settings.apply(world);
+    }
+}
@@ -1,30 +1,67 @@
 use bevy::{
+    camera::visibility::RenderLayers,
     color::{ColorCurve, palettes::tailwind},
+    core_pipeline::tonemapping::Tonemapping,
     ecs::{
         schedule::ScheduleConfigs,
-        system::{ScheduleSystem, SystemId},
+        system::{ScheduleSystem, SystemId, SystemParam},
     },
     prelude::*,
 };
 use bitflags::bitflags;
+use clap::ValueEnum;
 use num_enum::TryFromPrimitive;
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    input_buffer::InputTick,
+    simulation::BoidsMeta,
+    simulation::BouncingBallsMeta,
+    simulation::CameraFollowMeta,
+    simulation::CharacterMeta,
+    simulation::FountainMeta,
+    simulation::KeyframesMeta,
     simulation::LorenzAttractorMeta,
     simulation::MouseCursorMeta,
     simulation::MovingBarsMeta,
+    simulation::OrbitMeta,
+    simulation::ProjectilesMeta,
+    simulation::RapidFireMeta,
+    simulation::RopeMeta,
+    simulation::SmoothingMeta,
+    simulation::SpringChainMeta,
+    simulation::StochasticMeta,
+    simulation::WheelsMeta,
     timestep::{Fixed, NoDelta, SemiFixed, VariableDelta},
 };
 
-#[derive(Resource, Clone, Copy, Default)]
+#[derive(
+    Resource, Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize, ValueEnum,
+)]
 pub enum ActiveSimulation {
     #[default]
     LorenzAttractor = 1,
     MouseCursor,
     MovingBars,
+    Character,
+    BouncingBalls,
+    SpringChain,
+    Orbit,
+    Projectiles,
+    CameraFollow,
+    Smoothing,
+    Wheels,
+    Fountain,
+    Rope,
+    RapidFire,
+    Stochastic,
+    Keyframes,
+    Boids,
 }
 
-#[derive(TryFromPrimitive, Clone, Copy)]
+#[derive(
+    TryFromPrimitive, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize, ValueEnum,
+)]
 #[repr(u8)]
 pub enum Timestep {
     NoDelta = 1,
@@ -38,6 +75,16 @@ impl Timestep {
         (self as u8).ilog2() as usize
     }
 
+    /// Short name, for labels
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::NoDelta => "No Delta",
+            Self::VariableDelta => "Variable Delta",
+            Self::SemiFixed => "Semi-Fixed",
+            Self::Fixed => "Fixed",
+        }
+    }
+
     pub fn palette(self) -> ColorCurve<Oklaba> {
         match self {
             Self::NoDelta => ColorCurve::new([
@@ -82,6 +129,26 @@ pub trait CommandsExt {
         timestep: &Timestep,
         bundle: impl Bundle,
     ) -> EntityCommands<'_>;
+
+    /// Spawn an inactive camera for a simulation's render layer, for [`respawn`] to activate
+    fn spawn_simulation_camera(&mut self, render_layer: usize) -> EntityCommands<'_>;
+}
+
+pub trait EntityCommandsExt {
+    /// Replace whichever timestep the entity currently has
+    fn insert_timestep(&mut self, timestep: Timestep) -> &mut Self;
+}
+
+impl EntityCommandsExt for EntityCommands<'_> {
+    fn insert_timestep(&mut self, timestep: Timestep) -> &mut Self {
+        self.remove::<(NoDelta, VariableDelta, SemiFixed, Fixed)>();
+        match timestep {
+            Timestep::NoDelta => self.insert(NoDelta),
+            Timestep::VariableDelta => self.insert(VariableDelta),
+            Timestep::SemiFixed => self.insert(SemiFixed),
+            Timestep::Fixed => self.insert(Fixed),
+        }
+    }
 }
 
 impl CommandsExt for Commands<'_, '_> {
@@ -97,6 +164,20 @@ impl CommandsExt for Commands<'_, '_> {
             Timestep::Fixed => self.spawn((Fixed, bundle)),
         }
     }
+
+    fn spawn_simulation_camera(&mut self, render_layer: usize) -> EntityCommands<'_> {
+        self.spawn((
+            Camera2d,
+            Camera {
+                is_active: false,
+                ..default()
+            },
+            Tonemapping::None,
+            RenderLayers::layer(render_layer),
+            // Keep the origin to the right of the UI
+            Transform::from_xyz(-200.0, 0.0, 0.0),
+        ))
+    }
 }
 
 pub trait TimesteppedSystems {
@@ -109,19 +190,28 @@ pub trait AppExt {
 
 impl AppExt for App {
     fn add_systems_with_timestep<T: TimesteppedSystems>(&mut self) -> &mut Self {
-        self.add_systems(NoDelta, T::get_systems_for_timestep::<NoDelta>());
+        self.add_systems(
+            NoDelta,
+            T::get_systems_for_timestep::<NoDelta>().after(InputTick),
+        );
         self.add_systems(
             VariableDelta,
-            T::get_systems_for_timestep::<VariableDelta>(),
+            T::get_systems_for_timestep::<VariableDelta>().after(InputTick),
+        );
+        self.add_systems(
+            SemiFixed,
+            T::get_systems_for_timestep::<SemiFixed>().after(InputTick),
+        );
+        self.add_systems(
+            Fixed,
+            T::get_systems_for_timestep::<Fixed>().after(InputTick),
         );
-        self.add_systems(SemiFixed, T::get_systems_for_timestep::<SemiFixed>());
-        self.add_systems(Fixed, T::get_systems_for_timestep::<Fixed>());
         self
     }
 }
 
 bitflags! {
-    #[derive(Resource, Clone, Copy)]
+    #[derive(Resource, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
     pub struct ActiveTimesteps: u8 {
         const NO_DELTA = 1;
         const VARIABLE_DELTA = 2;
@@ -141,6 +231,14 @@ impl ActiveTimesteps {
         self.iter_names()
             .map(|(_, timestep)| timestep.bits().try_into().unwrap())
     }
+
+    /// Vertical centre of a timestep's lane, stacking a lane for each active timestep in the same
+    /// order as the UI, centred on the origin
+    pub fn lane_centre(&self, timestep: Timestep, lane_height: f32) -> f32 {
+        let total = self.bits().count_ones() as f32;
+        let lane = (self.bits() & ((timestep as u8) - 1)).count_ones() as f32;
+        ((total / 2.0) - lane - 0.5) * lane_height
+    }
 }
 
 impl From<Timestep> for ActiveTimesteps {
@@ -192,25 +290,65 @@ fn setup(mut commands: Commands) {
     commands.run_system_cached(respawn);
 }
 
+/// Every simulation's [`SimulationMeta`]
+#[derive(SystemParam)]
+pub struct SimulationMetas<'w> {
+    lorenz_attractor: Res<'w, LorenzAttractorMeta>,
+    mouse_cursor: Res<'w, MouseCursorMeta>,
+    moving_bars: Res<'w, MovingBarsMeta>,
+    character: Res<'w, CharacterMeta>,
+    bouncing_balls: Res<'w, BouncingBallsMeta>,
+    spring_chain: Res<'w, SpringChainMeta>,
+    orbit: Res<'w, OrbitMeta>,
+    projectiles: Res<'w, ProjectilesMeta>,
+    camera_follow: Res<'w, CameraFollowMeta>,
+    smoothing: Res<'w, SmoothingMeta>,
+    wheels: Res<'w, WheelsMeta>,
+    fountain: Res<'w, FountainMeta>,
+    rope: Res<'w, RopeMeta>,
+    rapid_fire: Res<'w, RapidFireMeta>,
+    stochastic: Res<'w, StochasticMeta>,
+    keyframes: Res<'w, KeyframesMeta>,
+    boids: Res<'w, BoidsMeta>,
+}
+
+impl SimulationMetas<'_> {
+    fn get(&self, simulation: ActiveSimulation) -> (Entity, SystemId<In<Timestep>>) {
+        match simulation {
+            ActiveSimulation::LorenzAttractor => self.lorenz_attractor.get(),
+            ActiveSimulation::MouseCursor => self.mouse_cursor.get(),
+            ActiveSimulation::MovingBars => self.moving_bars.get(),
+            ActiveSimulation::Character => self.character.get(),
+            ActiveSimulation::BouncingBalls => self.bouncing_balls.get(),
+            ActiveSimulation::SpringChain => self.spring_chain.get(),
+            ActiveSimulation::Orbit => self.orbit.get(),
+            ActiveSimulation::Projectiles => self.projectiles.get(),
+            ActiveSimulation::CameraFollow => self.camera_follow.get(),
+            ActiveSimulation::Smoothing => self.smoothing.get(),
+            ActiveSimulation::Wheels => self.wheels.get(),
+            ActiveSimulation::Fountain => self.fountain.get(),
+            ActiveSimulation::Rope => self.rope.get(),
+            ActiveSimulation::RapidFire => self.rapid_fire.get(),
+            ActiveSimulation::Stochastic => self.stochastic.get(),
+            ActiveSimulation::Keyframes => self.keyframes.get(),
+            ActiveSimulation::Boids => self.boids.get(),
+        }
+    }
+}
+
 pub fn respawn(
     mut commands: Commands,
     mut cameras: Query<(Entity, &mut Camera)>,
     active_simulation: Res<ActiveSimulation>,
     active_timesteps: Res<ActiveTimesteps>,
     despawn_systems: Res<DespawnSystems>,
-    lorenz_attractor: Res<LorenzAttractorMeta>,
-    mouse_cursor: Res<MouseCursorMeta>,
-    moving_bars: Res<MovingBarsMeta>,
+    simulations: SimulationMetas,
 ) {
     for &system in &despawn_systems.0 {
         commands.run_system(system);
     }
 
-    let (active_camera, spawn) = match *active_simulation {
-        ActiveSimulation::LorenzAttractor => lorenz_attractor.get(),
-        ActiveSimulation::MouseCursor => mouse_cursor.get(),
-        ActiveSimulation::MovingBars => moving_bars.get(),
-    };
+    let (active_camera, spawn) = simulations.get(*active_simulation);
 
     for (entity, mut camera) in cameras.iter_mut() {
         if active_camera == entity {
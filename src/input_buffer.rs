@@ -0,0 +1,92 @@
+//! Input handling that is safe to use from any timestep.
+//!
+//! [`ButtonInput::just_pressed`] is only valid for a single render frame, but the timesteps may update zero or more times
+//! per frame. So reading it from a simulation update will either miss presses entirely, or see the same press multiple times.
+//! Instead, key presses are collected every render frame, and handed out to exactly one update of each timestep.
+
+use bevy::{ecs::schedule::ScheduleLabel, input::InputSystems, prelude::*};
+use bitflags::Flags;
+
+use crate::{
+    configuration::{ActiveTimesteps, Timestep, TimestepComponent},
+    timestep::{Fixed, NoDelta, SemiFixed, VariableDelta},
+};
+
+/// Ran at the start of every simulation update, before any simulation systems
+#[derive(SystemSet, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct InputTick;
+
+/// Key presses, per timestep.
+///
+/// The bookkeeping done every frame bypasses change detection, so that only toggling [`InputBuffer::enabled`] counts as a change
+#[derive(Resource)]
+pub struct InputBuffer {
+    /// When disabled, each update sees the presses from the current render frame instead,
+    /// to demonstrate the problem that this solves
+    pub enabled: bool,
+    /// Collected, but not yet handed to an update. Indexed by [`Timestep::index`]
+    pending: [Vec<KeyCode>; ActiveTimesteps::FLAGS.len()],
+    /// Handed to the current update
+    current: [Vec<KeyCode>; ActiveTimesteps::FLAGS.len()],
+}
+
+impl Default for InputBuffer {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            pending: default(),
+            current: default(),
+        }
+    }
+}
+
+impl InputBuffer {
+    /// Whether the key was pressed since the previous update of this timestep
+    pub fn just_pressed(&self, timestep: Timestep, key: KeyCode) -> bool {
+        self.current[timestep.index()].contains(&key)
+    }
+
+    /// Every key pressed since the previous update of this timestep
+    pub fn get_just_pressed(&self, timestep: Timestep) -> impl Iterator<Item = &KeyCode> {
+        self.current[timestep.index()].iter()
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<InputBuffer>()
+        .add_systems(PreUpdate, collect_input.after(InputSystems));
+
+    add_input_tick::<NoDelta>(app);
+    add_input_tick::<VariableDelta>(app);
+    add_input_tick::<SemiFixed>(app);
+    add_input_tick::<Fixed>(app);
+}
+
+fn add_input_tick<T: TimestepComponent + ScheduleLabel + Default>(app: &mut App) {
+    app.add_systems(T::default(), begin_tick::<T>.in_set(InputTick));
+}
+
+fn collect_input(mut buffer: ResMut<InputBuffer>, keys: Res<ButtonInput<KeyCode>>) {
+    for pressed in &mut buffer.bypass_change_detection().pending {
+        pressed.extend(keys.get_just_pressed());
+    }
+}
+
+fn begin_tick<T: TimestepComponent>(
+    mut buffer: ResMut<InputBuffer>,
+    keys: Res<ButtonInput<KeyCode>>,
+) {
+    let index = T::TIMESTEP.index();
+    let buffer = buffer.bypass_change_detection();
+    let current = &mut buffer.current[index];
+    let pending = &mut buffer.pending[index];
+
+    current.clear();
+    if buffer.enabled {
+        std::mem::swap(current, pending);
+    } else {
+        // Naive behaviour, equivalent to reading `ButtonInput` directly
+        pending.clear();
+        current.extend(keys.get_just_pressed());
+    }
+}
@@ -5,6 +5,8 @@ use bevy::{
     ecs::{lifecycle::HookContext, world::DeferredWorld},
     prelude::*,
 };
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
 
 #[derive(Component, Default, Deref, DerefMut)]
 #[component(on_add = init_other_transforms)]
@@ -19,6 +21,10 @@ impl SimulationTransform {
 #[derive(Component, Deref, DerefMut)]
 struct PreviousTransform(Transform);
 
+/// Opt out of interpolation, so that the entity is always rendered at its latest simulated transform
+#[derive(Component)]
+pub struct NoInterpolation;
+
 fn init_other_transforms(mut world: DeferredWorld, context: HookContext) {
     let &SimulationTransform(transform) = world.get(context.entity).unwrap();
     world
@@ -27,7 +33,19 @@ fn init_other_transforms(mut world: DeferredWorld, context: HookContext) {
         .insert((transform, PreviousTransform(transform)));
 }
 
-#[derive(Resource, Component, Default, Clone, Copy)]
+#[derive(
+    Resource,
+    Component,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Debug,
+    Serialize,
+    Deserialize,
+    ValueEnum,
+)]
 pub enum InterpolationMode {
     /// No interpolation
     None,
@@ -40,7 +58,10 @@ pub enum InterpolationMode {
 
 pub fn plugin(app: &mut App) {
     app.init_resource::<InterpolationMode>()
-        .add_systems(PostUpdate, (update_non_fixed, interpolate_transforms))
+        .add_systems(
+            PostUpdate,
+            (update_non_fixed, interpolate_transforms).before(TransformSystems::Propagate),
+        )
         .add_systems(FixedPreUpdate, update_previous_transforms);
 }
 
@@ -53,7 +74,15 @@ fn update_non_fixed(mut non_fixed: Query<(&mut Transform, &SimulationTransform),
 }
 
 fn interpolate_transforms(
-    mut fixed: Query<(&mut Transform, &PreviousTransform, &SimulationTransform), With<Fixed>>,
+    mut fixed: Query<
+        (
+            &mut Transform,
+            &PreviousTransform,
+            &SimulationTransform,
+            Has<NoInterpolation>,
+        ),
+        With<Fixed>,
+    >,
     mode: Res<InterpolationMode>,
     time: Res<Time<bevy::prelude::Fixed>>,
 ) {
@@ -63,7 +92,8 @@ fn interpolate_transforms(
         InterpolationMode::Extrapolate => time.overstep_fraction() + 1.0,
     };
 
-    for (mut render, previous, simulation) in fixed.iter_mut() {
+    for (mut render, previous, simulation, no_interpolation) in fixed.iter_mut() {
+        let t = if no_interpolation { 1.0 } else { t };
         *render = Transform {
             translation: previous.translation.lerp(simulation.translation, t),
             rotation: previous.rotation.slerp(simulation.rotation, t),
@@ -2,20 +2,32 @@ use bevy::{
     dev_tools::fps_overlay::FpsOverlayPlugin, feathers::FeathersPlugins, prelude::*,
     window::PresentMode,
 };
+use clap::Parser;
 
 use crate::{
-    simulation::{lorenz_attractor_plugin, mouse_cursor_plugin, moving_bars_plugin},
+    cli::Args,
+    simulation::{
+        boids_plugin, bouncing_balls_plugin, camera_follow_plugin, character_plugin,
+        fountain_plugin, keyframes_plugin, lorenz_attractor_plugin, mouse_cursor_plugin,
+        moving_bars_plugin, orbit_plugin, projectiles_plugin, rapid_fire_plugin, rope_plugin,
+        smoothing_plugin, spring_chain_plugin, stochastic_plugin, wheels_plugin,
+    },
     timestep::SemiFixed,
 };
 
+mod cli;
 mod configuration;
+mod input_buffer;
 mod interpolation;
+mod settings;
 mod simulation;
 mod timestep;
 mod ui;
 mod update_cadence;
 
 fn main() -> AppExit {
+    let args = Args::parse();
+
     App::new()
         .add_plugins((
             DefaultPlugins.set(WindowPlugin {
@@ -30,13 +42,34 @@ fn main() -> AppExit {
         ))
         .add_plugins((
             configuration::plugin,
-            lorenz_attractor_plugin,
-            mouse_cursor_plugin,
-            moving_bars_plugin,
+            input_buffer::plugin,
             interpolation::plugin,
             timestep::plugin,
             ui::plugin,
             update_cadence::UpdateCadencePlugin::default().add_schedule(SemiFixed),
         ))
+        .add_plugins((
+            lorenz_attractor_plugin,
+            mouse_cursor_plugin,
+            moving_bars_plugin,
+            character_plugin,
+            bouncing_balls_plugin,
+            spring_chain_plugin,
+            orbit_plugin,
+            projectiles_plugin,
+            camera_follow_plugin,
+        ))
+        .add_plugins((
+            smoothing_plugin,
+            wheels_plugin,
+            fountain_plugin,
+            rope_plugin,
+            rapid_fire_plugin,
+            stochastic_plugin,
+            keyframes_plugin,
+            boids_plugin,
+        ))
+        // Overwrite the configuration from the previous plugins, so must come last
+        .add_plugins((settings::plugin, args))
         .run()
 }
@@ -0,0 +1,391 @@
+//! Snapshots of the app's configuration, that can be serialized to disk and applied back onto the world.
+
+use std::{fmt::Debug, fs, ops::RangeInclusive, path::PathBuf, time::Duration};
+
+use bevy::{
+    prelude::*,
+    time::common_conditions::on_real_timer,
+    window::{PresentMode, PrimaryWindow, WindowCloseRequested},
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{
+    configuration::{ActiveSimulation, ActiveTimesteps},
+    input_buffer::InputBuffer,
+    interpolation::InterpolationMode,
+    simulation::{
+        BoidCount, CameraFollowConfig, EmissionRate, LorenzConfig, MAX_BETA, MAX_BOID_COUNT,
+        MAX_EMISSION_RATE, MAX_EVENT_RATE, MAX_FIRE_RATE, MAX_RHO, MAX_SIGMA, MAX_START,
+        MAX_STIFFNESS, MAX_TRAIL_LENGTH, MAX_WHEEL_SPEED, MIN_BETA, MIN_BOID_COUNT,
+        MIN_EMISSION_RATE, MIN_EVENT_RATE, MIN_FIRE_RATE, MIN_RHO, MIN_SIGMA, MIN_STIFFNESS,
+        MIN_TRAIL_LENGTH, MIN_WHEEL_SPEED, RapidFireConfig, SpringStiffness, StochasticConfig,
+        WheelSpeed,
+    },
+    timestep::SimulationDelta,
+    ui::{
+        CachedWinitSettings, FullscreenMode, LagConfig, MAX_MAX_DELTA_MS, MAX_TIME_SCALE,
+        MIN_TIME_SCALE, SLIDER_PRECISION,
+    },
+};
+
+/// Where the configuration is remembered between launches
+const LAST_USED_FILE: &str = "last_used.ron";
+
+/// Every user-configurable setting in the app
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub simulation: ActiveSimulation,
+    pub timesteps: ActiveTimesteps,
+    /// Measured in Hz
+    pub simulation_rate: f32,
+    /// Relative speed of virtual time
+    pub time_scale: f32,
+    /// Largest frame delta virtual time will accept, in milliseconds. `None` for unclamped
+    pub max_delta_ms: Option<f32>,
+    pub interpolation: InterpolationMode,
+    pub input_buffering: bool,
+    pub lorenz: LorenzConfig,
+    pub spring_stiffness: f32,
+    /// Revolutions per second of the Wheels simulation
+    pub wheel_speed: f32,
+    /// Particles per second of each fountain in the Fountain simulation
+    pub emission_rate: f32,
+    pub rapid_fire: RapidFireConfig,
+    pub stochastic: StochasticConfig,
+    /// Boids per flock in the Boids simulation
+    pub boid_count: u32,
+    pub camera_follow: CameraFollowConfig,
+    pub lag: LagConfig,
+    pub update_modes: CachedWinitSettings,
+    pub present_mode: PresentMode,
+    pub window_mode: FullscreenMode,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            simulation: default(),
+            timesteps: default(),
+            simulation_rate: 64.0,
+            time_scale: 1.0,
+            max_delta_ms: Some(Time::<Virtual>::default().max_delta().as_secs_f32() * 1000.0),
+            interpolation: default(),
+            input_buffering: true,
+            lorenz: default(),
+            spring_stiffness: SpringStiffness::default().0,
+            wheel_speed: WheelSpeed::default().0,
+            emission_rate: EmissionRate::default().0,
+            rapid_fire: default(),
+            stochastic: default(),
+            boid_count: BoidCount::default().0,
+            camera_follow: default(),
+            lag: default(),
+            update_modes: default(),
+            present_mode: PresentMode::Mailbox,
+            window_mode: FullscreenMode::Windowed,
+        }
+    }
+}
+
+impl Settings {
+    /// Read the current configuration out of the world
+    pub fn capture(world: &mut World) -> Self {
+        let window = world
+            .query_filtered::<&Window, With<PrimaryWindow>>()
+            .single(world)
+            .ok();
+
+        Self {
+            present_mode: window.map_or(PresentMode::Mailbox, |window| window.present_mode),
+            window_mode: window.map_or(FullscreenMode::Windowed, |window| window.mode.into()),
+            simulation: *world.resource(),
+            timesteps: *world.resource(),
+            simulation_rate: world
+                .resource::<SimulationDelta>()
+                .0
+                .as_secs_f32()
+                .recip()
+                .round(),
+            time_scale: world.resource::<Time<Virtual>>().relative_speed(),
+            max_delta_ms: Some(world.resource::<Time<Virtual>>().max_delta())
+                .filter(|&max_delta| max_delta != Duration::MAX)
+                .map(|max_delta| max_delta.as_secs_f32() * 1000.0),
+            interpolation: *world.resource(),
+            input_buffering: world.resource::<InputBuffer>().enabled,
+            lorenz: world.resource::<LorenzConfig>().clone(),
+            spring_stiffness: world.resource::<SpringStiffness>().0,
+            wheel_speed: world.resource::<WheelSpeed>().0,
+            emission_rate: world.resource::<EmissionRate>().0,
+            rapid_fire: world.resource::<RapidFireConfig>().clone(),
+            stochastic: world.resource::<StochasticConfig>().clone(),
+            boid_count: world.resource::<BoidCount>().0,
+            camera_follow: world.resource::<CameraFollowConfig>().clone(),
+            lag: world.resource::<LagConfig>().clone(),
+            update_modes: world.resource::<CachedWinitSettings>().clone(),
+        }
+    }
+
+    /// Overwrite the current configuration.
+    ///
+    /// Doesn't respawn the simulation, so [`respawn`](crate::configuration::respawn) should be ran afterwards if needed.
+    /// UI widgets will update themselves to match.
+    pub fn apply(&self, world: &mut World) {
+        let settings = self.validated();
+
+        world.insert_resource(settings.simulation);
+        world.insert_resource(settings.timesteps);
+        world.insert_resource(SimulationDelta(Duration::from_secs_f32(
+            settings.simulation_rate.recip(),
+        )));
+        let mut time = world.resource_mut::<Time<Virtual>>();
+        time.set_relative_speed(settings.time_scale);
+        time.set_max_delta(settings.max_delta_ms.map_or(Duration::MAX, |max_delta| {
+            Duration::from_secs_f32(max_delta / 1000.0)
+        }));
+        world.insert_resource(settings.interpolation);
+        world.resource_mut::<InputBuffer>().enabled = settings.input_buffering;
+        world.insert_resource(settings.lorenz.clone());
+        world.insert_resource(SpringStiffness(settings.spring_stiffness));
+        world.insert_resource(WheelSpeed(settings.wheel_speed));
+        world.insert_resource(EmissionRate(settings.emission_rate));
+        world.insert_resource(settings.rapid_fire.clone());
+        world.insert_resource(settings.stochastic.clone());
+        world.insert_resource(BoidCount(settings.boid_count));
+        world.insert_resource(settings.camera_follow.clone());
+        world.insert_resource(settings.lag.clone());
+        world.insert_resource(settings.update_modes.clone());
+
+        let mut windows = world.query_filtered::<&mut Window, With<PrimaryWindow>>();
+        if let Ok(mut window) = windows.single_mut(world) {
+            window.present_mode = settings.present_mode;
+            window.mode = settings.window_mode.into();
+        }
+    }
+
+    /// Replace any out of range values with their defaults, as they can only have come from a hand-edited or corrupted file,
+    /// and would otherwise panic or misbehave once applied
+    fn validated(&self) -> Self {
+        let default = Self::default();
+        let mut settings = self.clone();
+
+        validate(
+            "simulation rate",
+            &mut settings.simulation_rate,
+            1.0..=SLIDER_PRECISION,
+            default.simulation_rate,
+        );
+        validate(
+            "time scale",
+            &mut settings.time_scale,
+            MIN_TIME_SCALE..=MAX_TIME_SCALE,
+            default.time_scale,
+        );
+        if let Some(max_delta_ms) = &mut settings.max_delta_ms {
+            validate(
+                "max delta",
+                max_delta_ms,
+                1.0..=MAX_MAX_DELTA_MS,
+                default.max_delta_ms.unwrap(),
+            );
+        }
+
+        let lorenz = &mut settings.lorenz;
+        validate(
+            "Lorenz sigma",
+            &mut lorenz.sigma,
+            (MIN_SIGMA as f64)..=(MAX_SIGMA as f64),
+            default.lorenz.sigma,
+        );
+        validate(
+            "Lorenz rho",
+            &mut lorenz.rho,
+            (MIN_RHO as f64)..=(MAX_RHO as f64),
+            default.lorenz.rho,
+        );
+        validate(
+            "Lorenz beta",
+            &mut lorenz.beta,
+            (MIN_BETA as f64)..=(MAX_BETA as f64),
+            default.lorenz.beta,
+        );
+        for (axis, name) in ["Lorenz start x", "Lorenz start y", "Lorenz start z"]
+            .into_iter()
+            .enumerate()
+        {
+            validate(
+                name,
+                &mut lorenz.start[axis],
+                (-MAX_START as f64)..=(MAX_START as f64),
+                default.lorenz.start[axis],
+            );
+        }
+        validate(
+            "Lorenz trail length",
+            &mut lorenz.trail_length,
+            MIN_TRAIL_LENGTH..=MAX_TRAIL_LENGTH,
+            default.lorenz.trail_length,
+        );
+
+        validate(
+            "spring stiffness",
+            &mut settings.spring_stiffness,
+            MIN_STIFFNESS..=MAX_STIFFNESS,
+            default.spring_stiffness,
+        );
+        validate(
+            "wheel speed",
+            &mut settings.wheel_speed,
+            MIN_WHEEL_SPEED..=MAX_WHEEL_SPEED,
+            default.wheel_speed,
+        );
+        validate(
+            "emission rate",
+            &mut settings.emission_rate,
+            MIN_EMISSION_RATE..=MAX_EMISSION_RATE,
+            default.emission_rate,
+        );
+        validate(
+            "fire rate",
+            &mut settings.rapid_fire.rate,
+            MIN_FIRE_RATE..=MAX_FIRE_RATE,
+            default.rapid_fire.rate,
+        );
+        validate(
+            "event rate",
+            &mut settings.stochastic.rate,
+            MIN_EVENT_RATE..=MAX_EVENT_RATE,
+            default.stochastic.rate,
+        );
+        validate(
+            "boid count",
+            &mut settings.boid_count,
+            MIN_BOID_COUNT..=MAX_BOID_COUNT,
+            default.boid_count,
+        );
+        validate(
+            "lag frequency",
+            &mut settings.lag.frames_delay,
+            0..=(SLIDER_PRECISION as u32),
+            default.lag.frames_delay,
+        );
+        validate(
+            "lag duration",
+            &mut settings.lag.lag_duration_ms,
+            0..=(SLIDER_PRECISION as u64),
+            default.lag.lag_duration_ms,
+        );
+        for (mode, default_mode, name) in [
+            (
+                &mut settings.update_modes.focused,
+                &default.update_modes.focused,
+                "focused update wait",
+            ),
+            (
+                &mut settings.update_modes.unfocused,
+                &default.update_modes.unfocused,
+                "unfocused update wait",
+            ),
+        ] {
+            validate(
+                name,
+                &mut mode.wait,
+                Duration::from_millis(1)..=Duration::from_secs(1),
+                default_mode.wait,
+            );
+        }
+
+        settings
+    }
+}
+
+/// Replace a value with its default if it's out of range, with a warning so that the problem doesn't go unnoticed
+fn validate<T: PartialOrd + Debug>(
+    name: &str,
+    value: &mut T,
+    range: RangeInclusive<T>,
+    default: T,
+) {
+    if !range.contains(value) {
+        warn!(
+            "Ignoring {name} of {value:?}, as it isn't between {:?} and {:?}. Using {default:?} instead",
+            range.start(),
+            range.end()
+        );
+        *value = default;
+    }
+}
+
+/// Restores the configuration from the previous launch, and keeps it saved as it changes.
+///
+/// Must be added after every plugin that initializes the configuration resources, so as to overwrite their defaults.
+pub fn plugin(app: &mut App) {
+    if let Some(settings) = load_config::<Settings>(LAST_USED_FILE) {
+        settings.apply(app.world_mut());
+    }
+
+    app.add_systems(
+        PreUpdate,
+        save_last_used
+            .run_if(on_real_timer(Duration::from_secs(1)).or(on_message::<WindowCloseRequested>)),
+    );
+}
+
+fn save_last_used(world: &mut World, mut last_saved: Local<Option<Settings>>) {
+    // Without the window we'd overwrite the presentation settings with defaults
+    let mut window = world.query_filtered::<(), With<PrimaryWindow>>();
+    if window.single(world).is_err() {
+        return;
+    }
+
+    let settings = Settings::capture(world);
+    if last_saved.as_ref() != Some(&settings) {
+        save_config(LAST_USED_FILE, &settings);
+        *last_saved = Some(settings);
+    }
+}
+
+/// Location of a file in this app's folder, inside the user's config directory
+pub fn config_path(file_name: &str) -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")).join(file_name))
+}
+
+/// Read a value from a RON file in the config directory.
+///
+/// Returns `None` if the file doesn't exist, and logs a warning if it couldn't be read.
+pub fn load_config<T: DeserializeOwned>(file_name: &str) -> Option<T> {
+    let path = config_path(file_name)?;
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return None,
+        Err(error) => {
+            warn!("Failed to read {}: {error}", path.display());
+            return None;
+        }
+    };
+
+    ron::from_str(&contents)
+        .inspect_err(|error| warn!("Failed to parse {}: {error}", path.display()))
+        .ok()
+}
+
+/// Write a value to a RON file in the config directory, logging a warning if it couldn't be written
+pub fn save_config<T: Serialize>(file_name: &str, value: &T) {
+    let Some(path) = config_path(file_name) else {
+        warn!("Couldn't find a config directory to save {file_name} to");
+        return;
+    };
+
+    let result = ron::ser::to_string_pretty(value, default())
+        .map_err(|error| error.to_string())
+        .and_then(|contents| {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+            }
+            fs::write(&path, contents).map_err(|error| error.to_string())
+        });
+
+    if let Err(error) = result {
+        warn!("Failed to save {}: {error}", path.display());
+    }
+}
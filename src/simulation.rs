@@ -1,9 +1,51 @@
+mod boids;
+mod bouncing_balls;
+mod camera_follow;
+mod character;
+mod fountain;
+mod keyframes;
 mod lorenz_attractor;
 mod mouse_cursor;
 mod moving_bars;
+mod orbit;
+mod projectiles;
+mod rapid_fire;
+mod rope;
+mod smoothing;
+mod spring_chain;
+mod stochastic;
+mod wheels;
 
 pub use {
-    lorenz_attractor::{LorenzAttractorMeta, plugin as lorenz_attractor_plugin},
+    boids::{BoidCount, BoidsMeta, MAX_BOID_COUNT, MIN_BOID_COUNT, plugin as boids_plugin},
+    bouncing_balls::{BouncingBallsMeta, plugin as bouncing_balls_plugin},
+    camera_follow::{CameraFollowConfig, CameraFollowMeta, plugin as camera_follow_plugin},
+    character::{CharacterMeta, plugin as character_plugin},
+    fountain::{
+        EmissionRate, FountainMeta, MAX_EMISSION_RATE, MIN_EMISSION_RATE, plugin as fountain_plugin,
+    },
+    keyframes::{KeyframesMeta, plugin as keyframes_plugin},
+    lorenz_attractor::{
+        DIVERGENCE_WINDOW, DivergenceReference, LORENZ_PRESETS, LorenzAttractorMeta, LorenzConfig,
+        LorenzDivergence, MAX_BETA, MAX_RHO, MAX_SIGMA, MAX_START, MAX_TRAIL_LENGTH, MIN_BETA,
+        MIN_RHO, MIN_SIGMA, MIN_TRAIL_LENGTH, plugin as lorenz_attractor_plugin,
+    },
     mouse_cursor::{MouseCursorMeta, plugin as mouse_cursor_plugin},
     moving_bars::{MovingBarsMeta, plugin as moving_bars_plugin},
+    orbit::{OrbitMeta, plugin as orbit_plugin},
+    projectiles::{ProjectilesMeta, plugin as projectiles_plugin},
+    rapid_fire::{
+        MAX_FIRE_RATE, MIN_FIRE_RATE, RapidFireConfig, RapidFireMeta, plugin as rapid_fire_plugin,
+    },
+    rope::{RopeMeta, plugin as rope_plugin},
+    smoothing::{SmoothingMeta, plugin as smoothing_plugin},
+    spring_chain::{
+        MAX_STIFFNESS, MIN_STIFFNESS, SpringChainMeta, SpringStiffness,
+        plugin as spring_chain_plugin,
+    },
+    stochastic::{
+        MAX_EVENT_RATE, MIN_EVENT_RATE, StochasticConfig, StochasticMeta,
+        plugin as stochastic_plugin,
+    },
+    wheels::{MAX_WHEEL_SPEED, MIN_WHEEL_SPEED, WheelSpeed, WheelsMeta, plugin as wheels_plugin},
 };
@@ -0,0 +1,259 @@
+use std::time::{Duration, Instant};
+
+use bevy::{
+    camera::visibility::RenderLayers,
+    ecs::{
+        schedule::ScheduleConfigs,
+        system::{ScheduleSystem, SystemId},
+    },
+    prelude::*,
+    sprite::Anchor,
+};
+
+use crate::{
+    configuration::{
+        AppExt, CommandsExt, DespawnSystems, SimulationMeta, Timestep, TimestepComponent,
+        TimesteppedSystems,
+    },
+    interpolation::SimulationTransform,
+};
+
+#[derive(Resource)]
+pub struct BoidsMeta {
+    pub camera: Entity,
+    pub spawn: SystemId<In<Timestep>>,
+}
+
+impl SimulationMeta for BoidsMeta {
+    fn get(&self) -> (Entity, SystemId<In<Timestep>>) {
+        (self.camera, self.spawn)
+    }
+}
+
+/// Number of boids in each active timestep's flock
+#[derive(Resource, Clone, Copy, PartialEq, Debug)]
+pub struct BoidCount(pub u32);
+
+impl Default for BoidCount {
+    fn default() -> Self {
+        Self(1000)
+    }
+}
+
+pub const MIN_BOID_COUNT: u32 = 10;
+pub const MAX_BOID_COUNT: u32 = 5000;
+
+#[derive(Component)]
+struct Boid {
+    velocity: Vec2,
+}
+
+#[derive(Component)]
+struct Flock {
+    timestep: Timestep,
+    /// How long the most recent update took to run
+    update_duration: Duration,
+}
+
+#[derive(GizmoConfigGroup, Default, Reflect)]
+struct BoidGizmos;
+
+/// The flocks are steered back whenever they leave this area, centred on the origin.
+/// Wrapping around instead would make interpolation streak boids across the whole area.
+const BOUNDS: Vec2 = Vec2::new(600.0, 600.0);
+const TURN: f32 = 400.0;
+/// Boids only react to others within this distance, which is also the size of each cell of the spatial grid
+const NEIGHBOUR_RADIUS: f32 = 40.0;
+const SEPARATION_RADIUS: f32 = 12.0;
+const SEPARATION: f32 = 2000.0;
+const ALIGNMENT: f32 = 1.0;
+const COHESION: f32 = 1.0;
+const MIN_SPEED: f32 = 80.0;
+const MAX_SPEED: f32 = 160.0;
+const SIZE: Vec2 = Vec2::new(6.0, 3.0);
+
+const RENDER_LAYER: usize = 16;
+
+struct Systems;
+
+impl TimesteppedSystems for Systems {
+    fn get_systems_for_timestep<T: TimestepComponent>() -> ScheduleConfigs<ScheduleSystem> {
+        run::<T>.into_configs()
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<BoidCount>()
+        .init_gizmo_group::<BoidGizmos>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, update_labels)
+        .add_systems(PostUpdate, draw)
+        .add_systems_with_timestep::<Systems>();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut despawns: ResMut<DespawnSystems>,
+    mut gizmos: ResMut<GizmoConfigStore>,
+) {
+    let despawn = commands.register_system(despawn);
+    despawns.0.push(despawn);
+    let spawn = commands.register_system(spawn);
+
+    let camera = commands.spawn_simulation_camera(RENDER_LAYER).id();
+    commands.insert_resource(BoidsMeta { camera, spawn });
+
+    gizmos.config_mut::<BoidGizmos>().0.render_layers = RenderLayers::layer(RENDER_LAYER);
+}
+
+fn despawn(mut commands: Commands, entities: Query<Entity, Or<(With<Boid>, With<Flock>)>>) {
+    for entity in entities.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn spawn(timestep: In<Timestep>, mut commands: Commands, count: Res<BoidCount>) {
+    let colour: Color = timestep.palette().sample_unchecked(0.0).into();
+
+    // Every flock starts out identical, spread evenly without any randomness, so that they only diverge through their timesteps
+    for i in 0..count.0 {
+        let i = i as f32;
+        let position =
+            (Vec2::new((i * 0.754_877_7).fract(), (i * 0.569_840_3).fract()) - 0.5) * BOUNDS;
+        let angle = i * 2.399_963;
+
+        commands.spawn_with_timestep(
+            &timestep.0,
+            (
+                Boid {
+                    velocity: Vec2::from_angle(angle) * MIN_SPEED,
+                },
+                SimulationTransform::from_xyz(position.x, position.y, 1.0),
+                RenderLayers::layer(RENDER_LAYER),
+                Sprite::from_color(colour, SIZE),
+            ),
+        );
+    }
+
+    commands.spawn_with_timestep(
+        &timestep.0,
+        (
+            Flock {
+                timestep: timestep.0,
+                update_duration: Duration::ZERO,
+            },
+            Text2d::default(),
+            TextFont::from_font_size(16.0),
+            TextColor(colour),
+            Anchor::TOP_LEFT,
+            Transform::from_xyz(
+                (BOUNDS.x / 2.0) + 16.0,
+                (BOUNDS.y / 2.0) - ((timestep.index() as f32) * 48.0),
+                0.0,
+            ),
+            RenderLayers::layer(RENDER_LAYER),
+        ),
+    );
+}
+
+fn run<T: TimestepComponent>(
+    mut boids: Query<(&mut Boid, &mut SimulationTransform), With<T>>,
+    mut flock: Single<&mut Flock, With<T>>,
+    time: Res<Time>,
+) {
+    let start = Instant::now();
+    let dt = time.delta_secs();
+
+    let (positions, velocities): (Vec<Vec2>, Vec<Vec2>) = boids
+        .iter()
+        .map(|(boid, transform)| (transform.translation.truncate(), boid.velocity))
+        .unzip();
+
+    // Bucket the boids into a grid, so that each one only checks its neighbouring cells
+    let columns = (BOUNDS.x / NEIGHBOUR_RADIUS).floor() as i32;
+    let rows = (BOUNDS.y / NEIGHBOUR_RADIUS).floor() as i32;
+    let cell = |position: Vec2| {
+        let cell =
+            (((position / BOUNDS) + 0.5) * Vec2::new(columns as f32, rows as f32)).as_ivec2();
+        cell.clamp(IVec2::ZERO, IVec2::new(columns - 1, rows - 1))
+    };
+    let mut grid = vec![Vec::new(); (columns * rows) as usize];
+    for (i, &position) in positions.iter().enumerate() {
+        let cell = cell(position);
+        grid[(cell.y * columns + cell.x) as usize].push(i);
+    }
+
+    for (i, (mut boid, mut transform)) in boids.iter_mut().enumerate() {
+        let position = positions[i];
+        let home = cell(position);
+
+        let mut count = 0;
+        let mut average_velocity = Vec2::ZERO;
+        let mut average_offset = Vec2::ZERO;
+        let mut separation = Vec2::ZERO;
+        for y in -1..=1 {
+            for x in -1..=1 {
+                let neighbour = home + IVec2::new(x, y);
+                if neighbour.x < 0
+                    || neighbour.y < 0
+                    || neighbour.x >= columns
+                    || neighbour.y >= rows
+                {
+                    continue;
+                }
+
+                for &j in &grid[(neighbour.y * columns + neighbour.x) as usize] {
+                    let offset = positions[j] - position;
+                    let distance = offset.length();
+                    if j == i || distance > NEIGHBOUR_RADIUS {
+                        continue;
+                    }
+
+                    count += 1;
+                    average_velocity += velocities[j];
+                    average_offset += offset;
+                    if distance < SEPARATION_RADIUS {
+                        separation -= offset / distance.max(1.0).powi(2);
+                    }
+                }
+            }
+        }
+
+        let mut acceleration = separation * SEPARATION;
+        let outside = Vec2::select(
+            position.abs().cmpgt(BOUNDS / 2.0),
+            position.signum(),
+            Vec2::ZERO,
+        );
+        acceleration -= outside * TURN;
+        if count > 0 {
+            let count = count as f32;
+            acceleration += ((average_velocity / count) - boid.velocity) * ALIGNMENT;
+            acceleration += (average_offset / count) * COHESION;
+        }
+
+        let velocity = boid.velocity + (acceleration * dt);
+        boid.velocity = velocity.clamp_length(MIN_SPEED, MAX_SPEED);
+
+        let position = position + (boid.velocity * dt);
+        transform.translation = position.extend(transform.translation.z);
+        transform.rotation = Quat::from_rotation_z(boid.velocity.to_angle());
+    }
+
+    flock.update_duration = start.elapsed();
+}
+
+fn draw(mut gizmos: Gizmos<BoidGizmos>) {
+    gizmos.rect_2d(Isometry2d::IDENTITY, BOUNDS, Color::WHITE.with_alpha(0.25));
+}
+
+fn update_labels(mut flocks: Query<(&mut Text2d, &Flock)>, count: Res<BoidCount>) {
+    for (mut text, flock) in flocks.iter_mut() {
+        text.0 = format!(
+            "{}: {} boids\n{:.2} ms per update",
+            flock.timestep.name(),
+            count.0,
+            flock.update_duration.as_secs_f64() * 1000.0,
+        );
+    }
+}
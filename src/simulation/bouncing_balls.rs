@@ -0,0 +1,200 @@
+use std::f32::consts::PI;
+
+use bevy::{
+    camera::visibility::RenderLayers,
+    ecs::{
+        schedule::ScheduleConfigs,
+        system::{ScheduleSystem, SystemId},
+    },
+    prelude::*,
+    sprite::Anchor,
+};
+
+use crate::{
+    configuration::{
+        AppExt, CommandsExt, DespawnSystems, SimulationMeta, Timestep, TimestepComponent,
+        TimesteppedSystems,
+    },
+    interpolation::SimulationTransform,
+};
+
+#[derive(Resource)]
+pub struct BouncingBallsMeta {
+    pub camera: Entity,
+    pub spawn: SystemId<In<Timestep>>,
+}
+
+impl SimulationMeta for BouncingBallsMeta {
+    fn get(&self) -> (Entity, SystemId<In<Timestep>>) {
+        (self.camera, self.spawn)
+    }
+}
+
+#[derive(Component)]
+struct Ball {
+    velocity: Vec2,
+    /// Passed through a wall, and is flying away from the box
+    escaped: bool,
+}
+
+/// Number of times a ball of this timestep has passed through a wall
+#[derive(Component)]
+struct TunnelingCount {
+    timestep: Timestep,
+    count: u32,
+}
+
+#[derive(Component)]
+struct Wall;
+
+const BALL_COUNT: u32 = 16;
+const BALL_RADIUS: f32 = 6.0;
+const MIN_SPEED: f32 = 600.0;
+const MAX_SPEED: f32 = 1800.0;
+
+/// Distance from the centre of the box to the centre of each wall
+const BOX_EXTENT: f32 = 250.0;
+const WALL_THICKNESS: f32 = 4.0;
+/// Escaped balls are relaunched from the centre once they're this far away
+const ESCAPE_DISTANCE: f32 = BOX_EXTENT * 4.0;
+
+const RENDER_LAYER: usize = 4;
+
+struct Systems;
+
+impl TimesteppedSystems for Systems {
+    fn get_systems_for_timestep<T: TimestepComponent>() -> ScheduleConfigs<ScheduleSystem> {
+        run::<T>.into_configs()
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(Startup, setup)
+        .add_systems(Update, update_labels)
+        .add_systems_with_timestep::<Systems>();
+}
+
+fn setup(mut commands: Commands, mut despawns: ResMut<DespawnSystems>) {
+    let despawn = commands.register_system(despawn);
+    despawns.0.push(despawn);
+    let spawn = commands.register_system(spawn);
+
+    let camera = commands.spawn_simulation_camera(RENDER_LAYER).id();
+    commands.insert_resource(BouncingBallsMeta { camera, spawn });
+
+    let length = (BOX_EXTENT * 2.0) + WALL_THICKNESS;
+    for (position, size) in [
+        (Vec2::X, Vec2::new(WALL_THICKNESS, length)),
+        (Vec2::NEG_X, Vec2::new(WALL_THICKNESS, length)),
+        (Vec2::Y, Vec2::new(length, WALL_THICKNESS)),
+        (Vec2::NEG_Y, Vec2::new(length, WALL_THICKNESS)),
+    ] {
+        commands.spawn((
+            Wall,
+            Transform::from_translation((position * BOX_EXTENT).extend(0.0)),
+            RenderLayers::layer(RENDER_LAYER),
+            Sprite::from_color(Color::WHITE, size),
+        ));
+    }
+}
+
+fn despawn(
+    mut commands: Commands,
+    entities: Query<Entity, Or<(With<Ball>, With<TunnelingCount>)>>,
+) {
+    for entity in entities.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn spawn(timestep: In<Timestep>, mut commands: Commands) {
+    let colour: Color = timestep.palette().sample_unchecked(0.0).into();
+
+    // Every timestep launches the same balls, so that they only differ by how they're integrated
+    for i in 0..BALL_COUNT {
+        let fraction = (i as f32) / (BALL_COUNT as f32);
+        // Offset from the axes, so that no ball bounces back and forth along a single line
+        let direction = Vec2::from_angle((fraction * PI * 2.0) + 0.3);
+        let speed = MIN_SPEED + ((MAX_SPEED - MIN_SPEED) * fraction);
+        commands.spawn_with_timestep(
+            &timestep.0,
+            (
+                Ball {
+                    velocity: direction * speed,
+                    escaped: false,
+                },
+                SimulationTransform::from_xyz(0.0, 0.0, 1.0),
+                RenderLayers::layer(RENDER_LAYER),
+                Sprite::from_color(colour, Vec2::splat(BALL_RADIUS * 2.0)),
+            ),
+        );
+    }
+
+    let y = -BOX_EXTENT - 16.0 - ((timestep.index() as f32) * 24.0);
+    commands.spawn_with_timestep(
+        &timestep.0,
+        (
+            TunnelingCount {
+                timestep: timestep.0,
+                count: 0,
+            },
+            Text2d::default(),
+            TextFont::from_font_size(18.0),
+            TextColor(colour),
+            Anchor::TOP_LEFT,
+            Transform::from_xyz(-BOX_EXTENT, y, 0.0),
+            RenderLayers::layer(RENDER_LAYER),
+        ),
+    );
+}
+
+fn run<T: TimestepComponent>(
+    mut balls: Query<(&mut Ball, &mut SimulationTransform), With<T>>,
+    mut count: Single<&mut TunnelingCount, With<T>>,
+    time: Res<Time>,
+) {
+    // Collisions are only checked at the end of each update, so a ball that moves further than this in a single update
+    // can end up on the far side of a wall without ever having touched it
+    const INSIDE: f32 = BOX_EXTENT - (WALL_THICKNESS / 2.0) - BALL_RADIUS;
+    const OUTSIDE: f32 = BOX_EXTENT + (WALL_THICKNESS / 2.0) + BALL_RADIUS;
+
+    for (mut ball, mut transform) in balls.iter_mut() {
+        let mut position = transform.translation.truncate() + (ball.velocity * time.delta_secs());
+
+        if ball.escaped {
+            if position.length() > ESCAPE_DISTANCE {
+                position = Vec2::ZERO;
+                ball.escaped = false;
+            }
+        } else {
+            for axis in 0..2 {
+                let distance = position[axis].abs();
+                if distance >= OUTSIDE {
+                    ball.escaped = true;
+                    count.count += 1;
+                    break;
+                }
+
+                if distance > INSIDE {
+                    let side = position[axis].signum();
+                    position[axis] = side * INSIDE;
+                    if ball.velocity[axis] * side > 0.0 {
+                        ball.velocity[axis] = -ball.velocity[axis];
+                    }
+                }
+            }
+        }
+
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
+
+fn update_labels(mut labels: Query<(&mut Text2d, &TunnelingCount), Changed<TunnelingCount>>) {
+    for (mut text, tunneling) in labels.iter_mut() {
+        text.0 = format!(
+            "{}: {} balls tunneled",
+            tunneling.timestep.name(),
+            tunneling.count
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    camera::visibility::RenderLayers,
+    core_pipeline::tonemapping::Tonemapping,
+    ecs::{
+        schedule::ScheduleConfigs,
+        system::{ScheduleSystem, SystemId},
+    },
+    prelude::*,
+    sprite::Anchor,
+    time::common_conditions::paused,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    configuration::{
+        AppExt, CommandsExt, DespawnSystems, EntityCommandsExt, SimulationMeta, Timestep,
+        TimestepComponent, TimesteppedSystems,
+    },
+    interpolation::{NoInterpolation, SimulationTransform},
+    timestep::{Fixed, NoDelta, SemiFixed, VariableDelta},
+};
+
+#[derive(Resource)]
+pub struct CameraFollowMeta {
+    pub camera: Entity,
+    pub spawn: SystemId<In<Timestep>>,
+}
+
+impl SimulationMeta for CameraFollowMeta {
+    fn get(&self) -> (Entity, SystemId<In<Timestep>>) {
+        (self.camera, self.spawn)
+    }
+}
+
+/// Which timesteps move the target and the camera.
+///
+/// Interpolation only has an effect on whichever of the two is driven by the Fixed timestep.
+#[derive(Resource, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CameraFollowConfig {
+    pub target: Timestep,
+    pub camera: Timestep,
+    pub interpolate_target: bool,
+    pub interpolate_camera: bool,
+}
+
+impl Default for CameraFollowConfig {
+    fn default() -> Self {
+        // The classic mistake, of an interpolated target followed by a camera updated every frame
+        Self {
+            target: Timestep::Fixed,
+            camera: Timestep::VariableDelta,
+            interpolate_target: true,
+            interpolate_camera: false,
+        }
+    }
+}
+
+#[derive(Component)]
+struct Target;
+
+#[derive(Component)]
+struct FollowCamera;
+
+/// How far the target has drifted on screen from where the camera is meant to hold it, each frame
+#[derive(Resource, Default)]
+struct Jitter(VecDeque<f32>);
+
+#[derive(GizmoConfigGroup, Default, Reflect)]
+struct CameraFollowGizmos;
+
+const TARGET_SPEED: f32 = 300.0;
+/// Distance the camera is held behind the target, so that the target is to the right of the UI
+const CAMERA_LEAD: f32 = 200.0;
+const POST_SPACING: f32 = 80.0;
+
+/// Pixels of jitter shown at the top and bottom of the graph
+const GRAPH_RANGE: f32 = 20.0;
+/// Relative to the camera
+const GRAPH_ORIGIN: Vec2 = Vec2::new(100.0, 150.0);
+const GRAPH_SIZE: Vec2 = Vec2::new(300.0, 150.0);
+/// Number of frames shown in the graph
+const HISTORY_LENGTH: usize = 300;
+
+const RENDER_LAYER: usize = 8;
+
+struct Systems;
+
+impl TimesteppedSystems for Systems {
+    fn get_systems_for_timestep<T: TimestepComponent>() -> ScheduleConfigs<ScheduleSystem> {
+        // The camera follows wherever the target was simulated to be, as most follow cameras do
+        (move_target::<T>, move_camera::<T>).chain()
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<CameraFollowConfig>()
+        .init_resource::<Jitter>()
+        .init_gizmo_group::<CameraFollowGizmos>()
+        .add_systems(Startup, setup)
+        .add_systems(
+            PostUpdate,
+            (record_jitter.run_if(not(paused)), draw)
+                .chain()
+                .after(TransformSystems::Propagate),
+        )
+        .add_systems_with_timestep::<Systems>();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut despawns: ResMut<DespawnSystems>,
+    mut gizmos: ResMut<GizmoConfigStore>,
+) {
+    let despawn = commands.register_system(despawn);
+    despawns.0.push(despawn);
+    let spawn = commands.register_system(spawn);
+
+    let camera = commands
+        .spawn((
+            FollowCamera,
+            Camera2d,
+            Camera {
+                is_active: false,
+                ..default()
+            },
+            Tonemapping::None,
+            RenderLayers::layer(RENDER_LAYER),
+            children![(
+                Text2d::new(format!(
+                    "Target's distance from where the camera should hold it (±{GRAPH_RANGE:.0} px)"
+                )),
+                TextFont::from_font_size(16.0),
+                Anchor::BOTTOM_LEFT,
+                Transform::from_translation(
+                    (GRAPH_ORIGIN + Vec2::new(0.0, GRAPH_SIZE.y + 4.0)).extend(0.0)
+                ),
+                RenderLayers::layer(RENDER_LAYER),
+            )],
+        ))
+        .id();
+    commands.insert_resource(CameraFollowMeta { camera, spawn });
+
+    gizmos.config_mut::<CameraFollowGizmos>().0.render_layers = RenderLayers::layer(RENDER_LAYER);
+}
+
+fn despawn(
+    mut commands: Commands,
+    targets: Query<Entity, With<Target>>,
+    camera: Single<Entity, With<FollowCamera>>,
+    mut jitter: ResMut<Jitter>,
+) {
+    for entity in targets.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    commands.entity(*camera).remove::<(
+        SimulationTransform,
+        NoInterpolation,
+        NoDelta,
+        VariableDelta,
+        SemiFixed,
+        Fixed,
+    )>();
+    jitter.0.clear();
+}
+
+/// Ignores which timesteps are active, and uses the configured ones instead
+fn spawn(
+    _: In<Timestep>,
+    mut commands: Commands,
+    targets: Query<(), With<Target>>,
+    camera: Single<Entity, With<FollowCamera>>,
+    config: Res<CameraFollowConfig>,
+) {
+    // Called once per active timestep, but there's only one target
+    if !targets.is_empty() {
+        return;
+    }
+
+    let colour: Color = config.target.palette().sample_unchecked(0.0).into();
+    let mut target = commands.spawn_with_timestep(
+        &config.target,
+        (
+            Target,
+            SimulationTransform::from_xyz(0.0, 0.0, 1.0),
+            RenderLayers::layer(RENDER_LAYER),
+            Sprite::from_color(colour, Vec2::splat(40.0)),
+        ),
+    );
+    if !config.interpolate_target {
+        target.insert(NoInterpolation);
+    }
+
+    let mut camera = commands.entity(*camera);
+    camera
+        .insert_timestep(config.camera)
+        .insert(SimulationTransform::from_xyz(-CAMERA_LEAD, 0.0, 0.0));
+    if !config.interpolate_camera {
+        camera.insert(NoInterpolation);
+    }
+}
+
+fn move_target<T: TimestepComponent>(
+    mut target: Single<&mut SimulationTransform, (With<Target>, With<T>)>,
+    time: Res<Time>,
+) {
+    target.translation.x += TARGET_SPEED * time.delta_secs();
+}
+
+fn move_camera<T: TimestepComponent>(
+    mut camera: Single<&mut SimulationTransform, (With<FollowCamera>, With<T>)>,
+    target: Single<&SimulationTransform, (With<Target>, Without<FollowCamera>)>,
+) {
+    camera.translation.x = target.translation.x - CAMERA_LEAD;
+}
+
+fn record_jitter(
+    mut jitter: ResMut<Jitter>,
+    target: Single<&GlobalTransform, With<Target>>,
+    camera: Single<&GlobalTransform, With<FollowCamera>>,
+) {
+    let offset = target.translation().x - camera.translation().x;
+    jitter.0.push_back(offset - CAMERA_LEAD);
+    if jitter.0.len() > HISTORY_LENGTH {
+        jitter.0.pop_front();
+    }
+}
+
+fn draw(
+    camera: Single<&GlobalTransform, (With<FollowCamera>, With<SimulationTransform>)>,
+    jitter: Res<Jitter>,
+    config: Res<CameraFollowConfig>,
+    mut gizmos: Gizmos<CameraFollowGizmos>,
+) {
+    let camera = camera.translation().truncate();
+
+    // Scrolling scenery, so that the camera's movement is visible
+    let first = ((camera.x - 800.0) / POST_SPACING).floor() as i32;
+    for i in first..(first + (1600.0 / POST_SPACING) as i32) {
+        let x = (i as f32) * POST_SPACING;
+        gizmos.line_2d(
+            Vec2::new(x, -20.0),
+            Vec2::new(x, -60.0),
+            Color::WHITE.with_alpha(0.5),
+        );
+    }
+    gizmos.line_2d(
+        Vec2::new(camera.x - 800.0, -20.0),
+        Vec2::new(camera.x + 800.0, -20.0),
+        Color::WHITE,
+    );
+
+    let origin = camera + GRAPH_ORIGIN;
+    gizmos.rect_2d(
+        origin + (GRAPH_SIZE / 2.0),
+        GRAPH_SIZE,
+        Color::WHITE.with_alpha(0.5),
+    );
+    gizmos.line_2d(
+        origin + Vec2::new(0.0, GRAPH_SIZE.y / 2.0),
+        origin + Vec2::new(GRAPH_SIZE.x, GRAPH_SIZE.y / 2.0),
+        Color::WHITE.with_alpha(0.25),
+    );
+
+    let colour = config.target.palette().sample_unchecked(0.0);
+    let points = jitter.0.iter().enumerate().map(|(i, &offset)| {
+        let x = (i as f32) / (HISTORY_LENGTH as f32);
+        let y = ((offset / GRAPH_RANGE).clamp(-1.0, 1.0) + 1.0) / 2.0;
+        origin + (Vec2::new(x, y) * GRAPH_SIZE)
+    });
+    gizmos.linestrip_2d(points, colour);
+}
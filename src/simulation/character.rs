@@ -0,0 +1,259 @@
+use bevy::{
+    camera::visibility::RenderLayers,
+    ecs::{
+        schedule::ScheduleConfigs,
+        system::{ScheduleSystem, SystemId},
+    },
+    input::InputSystems,
+    prelude::*,
+    sprite::Anchor,
+};
+
+use crate::{
+    configuration::{
+        ActiveTimesteps, AppExt, CommandsExt, DespawnSystems, SimulationMeta, Timestep,
+        TimestepComponent, TimesteppedSystems,
+    },
+    input_buffer::InputBuffer,
+    interpolation::SimulationTransform,
+};
+
+#[derive(Resource)]
+pub struct CharacterMeta {
+    pub camera: Entity,
+    pub spawn: SystemId<In<Timestep>>,
+}
+
+impl SimulationMeta for CharacterMeta {
+    fn get(&self) -> (Entity, SystemId<In<Timestep>>) {
+        (self.camera, self.spawn)
+    }
+}
+
+#[derive(Component)]
+struct Character {
+    velocity: Vec2,
+    /// Y position of the character while standing on the ground
+    ground: f32,
+    /// Highest point of the current jump, above the ground
+    peak: f32,
+    /// Height of the most recently completed jump
+    jump_height: f32,
+    /// Number of jump presses this character's timestep has seen
+    jump_presses: u32,
+}
+
+/// Everything spawned alongside a character, that isn't simulated
+#[derive(Component)]
+struct Scenery;
+
+/// Shows the height of the character's most recent jump
+#[derive(Component)]
+struct ApexMarker(Entity);
+
+#[derive(Component)]
+struct CharacterLabel(Entity);
+
+/// Jump presses from every render frame, to compare against what each timestep saw
+#[derive(Resource, Default)]
+struct JumpPresses(u32);
+
+const LEFT: KeyCode = KeyCode::KeyA;
+const RIGHT: KeyCode = KeyCode::KeyD;
+const JUMP: KeyCode = KeyCode::KeyW;
+const FAST_FALL: KeyCode = KeyCode::KeyS;
+
+const SIZE: f32 = 32.0;
+const LANE_HEIGHT: f32 = 180.0;
+const MAX_X: f32 = 300.0;
+
+const ACCELERATION: f32 = 2400.0;
+const FRICTION: f32 = 1800.0;
+const MAX_SPEED: f32 = 400.0;
+const GRAVITY: f32 = 2000.0;
+const FAST_FALL_GRAVITY: f32 = GRAVITY * 3.0;
+const JUMP_SPEED: f32 = 700.0;
+
+const RENDER_LAYER: usize = 3;
+
+struct Systems;
+
+impl TimesteppedSystems for Systems {
+    fn get_systems_for_timestep<T: TimestepComponent>() -> ScheduleConfigs<ScheduleSystem> {
+        run::<T>.into_configs()
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<JumpPresses>()
+        .add_systems(Startup, setup)
+        .add_systems(PreUpdate, count_jump_presses.after(InputSystems))
+        .add_systems(Update, (update_apex_markers, update_labels))
+        .add_systems_with_timestep::<Systems>();
+}
+
+fn setup(mut commands: Commands, mut despawns: ResMut<DespawnSystems>) {
+    let despawn = commands.register_system(despawn);
+    despawns.0.push(despawn);
+    let spawn = commands.register_system(spawn);
+
+    let camera = commands.spawn_simulation_camera(RENDER_LAYER).id();
+    commands.insert_resource(CharacterMeta { camera, spawn });
+}
+
+fn despawn(
+    mut commands: Commands,
+    entities: Query<Entity, Or<(With<Character>, With<Scenery>)>>,
+    mut jump_presses: ResMut<JumpPresses>,
+) {
+    for entity in entities.iter() {
+        commands.entity(entity).despawn();
+    }
+    jump_presses.0 = 0;
+}
+
+fn spawn(timestep: In<Timestep>, mut commands: Commands, active_timesteps: Res<ActiveTimesteps>) {
+    let colour: Color = timestep.palette().sample_unchecked(0.0).into();
+
+    let floor =
+        active_timesteps.lane_centre(timestep.0, LANE_HEIGHT) - (LANE_HEIGHT / 2.0) + (SIZE / 2.0);
+    let ground = floor + (SIZE / 2.0);
+
+    let character = commands
+        .spawn_with_timestep(
+            &timestep.0,
+            (
+                Character {
+                    velocity: Vec2::ZERO,
+                    ground,
+                    peak: 0.0,
+                    jump_height: 0.0,
+                    jump_presses: 0,
+                },
+                SimulationTransform::from_xyz(0.0, ground, 1.0),
+                RenderLayers::layer(RENDER_LAYER),
+                Sprite::from_color(colour, Vec2::splat(SIZE)),
+            ),
+        )
+        .id();
+
+    commands.spawn((
+        Scenery,
+        Transform::from_xyz(0.0, floor - 1.0, 0.0),
+        RenderLayers::layer(RENDER_LAYER),
+        Sprite::from_color(Color::WHITE, Vec2::new((MAX_X + SIZE) * 2.0, 2.0)),
+    ));
+    commands.spawn((
+        Scenery,
+        ApexMarker(character),
+        Transform::from_xyz(0.0, ground, 0.0),
+        RenderLayers::layer(RENDER_LAYER),
+        Sprite::from_color(colour.with_alpha(0.5), Vec2::new((MAX_X + SIZE) * 2.0, 1.0)),
+    ));
+    commands.spawn((
+        Scenery,
+        CharacterLabel(character),
+        Text2d::default(),
+        TextFont::from_font_size(16.0),
+        TextColor(colour),
+        Anchor::TOP_LEFT,
+        Transform::from_xyz(-MAX_X - SIZE, floor - 8.0, 0.0),
+        RenderLayers::layer(RENDER_LAYER),
+    ));
+}
+
+fn count_jump_presses(mut jump_presses: ResMut<JumpPresses>, keys: Res<ButtonInput<KeyCode>>) {
+    if keys.just_pressed(JUMP) {
+        jump_presses.0 += 1;
+    }
+}
+
+fn run<T: TimestepComponent>(
+    mut characters: Query<(&mut Character, &mut SimulationTransform), With<T>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    input: Res<InputBuffer>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+    // Held keys are safe to read directly, only presses need to be buffered
+    let direction = (keys.pressed(RIGHT) as i8 - keys.pressed(LEFT) as i8) as f32;
+    let jump = input.just_pressed(T::TIMESTEP, JUMP);
+    let gravity = if keys.pressed(FAST_FALL) {
+        FAST_FALL_GRAVITY
+    } else {
+        GRAVITY
+    };
+
+    for (mut character, mut transform) in characters.iter_mut() {
+        let Character {
+            velocity,
+            ground,
+            peak,
+            jump_height,
+            jump_presses,
+        } = &mut *character;
+        let grounded = transform.translation.y <= *ground;
+
+        if direction == 0.0 {
+            velocity.x = velocity.x.signum() * (velocity.x.abs() - (FRICTION * dt)).max(0.0);
+        } else {
+            velocity.x += direction * ACCELERATION * dt;
+        }
+        velocity.x = velocity.x.clamp(-MAX_SPEED, MAX_SPEED);
+
+        if jump {
+            *jump_presses += 1;
+            if grounded {
+                velocity.y = JUMP_SPEED;
+            }
+        }
+        velocity.y -= gravity * dt;
+
+        // Semi-implicit Euler
+        let mut position = transform.translation.truncate() + (*velocity * dt);
+        if position.x.abs() > MAX_X {
+            position.x = position.x.clamp(-MAX_X, MAX_X);
+            velocity.x = 0.0;
+        }
+
+        if position.y <= *ground {
+            if !grounded {
+                *jump_height = *peak;
+                *peak = 0.0;
+            }
+            position.y = *ground;
+            velocity.y = 0.0;
+        } else {
+            *peak = peak.max(position.y - *ground);
+        }
+
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
+
+fn update_apex_markers(
+    mut markers: Query<(&mut Transform, &ApexMarker)>,
+    characters: Query<&Character, Changed<Character>>,
+) {
+    for (mut transform, marker) in markers.iter_mut() {
+        if let Ok(character) = characters.get(marker.0) {
+            transform.translation.y = character.ground + character.jump_height;
+        }
+    }
+}
+
+fn update_labels(
+    mut labels: Query<(&mut Text2d, &CharacterLabel)>,
+    characters: Query<&Character>,
+    jump_presses: Res<JumpPresses>,
+) {
+    for (mut text, label) in labels.iter_mut() {
+        let Ok(character) = characters.get(label.0) else {
+            continue;
+        };
+        text.0 = format!(
+            "Jump height: {:.1}   Jump presses seen: {} of {}",
+            character.jump_height, character.jump_presses, jump_presses.0
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use bevy::{
+    camera::visibility::RenderLayers,
+    ecs::{
+        schedule::ScheduleConfigs,
+        system::{ScheduleSystem, SystemId},
+    },
+    prelude::*,
+    sprite::Anchor,
+};
+
+use crate::{
+    configuration::{
+        AppExt, CommandsExt, DespawnSystems, SimulationMeta, Timestep, TimestepComponent,
+        TimesteppedSystems,
+    },
+    interpolation::SimulationTransform,
+};
+
+#[derive(Resource)]
+pub struct FountainMeta {
+    pub camera: Entity,
+    pub spawn: SystemId<In<Timestep>>,
+}
+
+impl SimulationMeta for FountainMeta {
+    fn get(&self) -> (Entity, SystemId<In<Timestep>>) {
+        (self.camera, self.spawn)
+    }
+}
+
+/// Particles emitted per second by every fountain
+#[derive(Resource, Clone, Copy, PartialEq, Debug)]
+pub struct EmissionRate(pub f32);
+
+impl Default for EmissionRate {
+    fn default() -> Self {
+        Self(100.0)
+    }
+}
+
+pub const MIN_EMISSION_RATE: f32 = 1.0;
+pub const MAX_EMISSION_RATE: f32 = 5000.0;
+
+#[derive(Component)]
+struct Fountain {
+    timestep: Timestep,
+    /// Whether to carry fractional particles over between updates, and spread each update's particles over its delta time
+    accurate: bool,
+    /// Fractional particles owed from previous updates
+    owed: f32,
+    emitted: u32,
+    expected: f32,
+}
+
+#[derive(Component)]
+struct Particle {
+    velocity: Vec2,
+    age: f32,
+}
+
+const GRAVITY: f32 = 1000.0;
+/// Exponential decay rate of the velocity, applied exactly regardless of delta time
+const DRAG: f32 = 0.5;
+const LAUNCH_SPEED: f32 = 700.0;
+/// Largest angle from vertical of each launch, in radians
+const SPREAD: f32 = 0.2;
+/// Seconds before each particle is despawned
+const LIFETIME: f32 = 2.0;
+const PARTICLE_SIZE: f32 = 3.0;
+
+const GROUND: f32 = -300.0;
+const SPACING: f32 = 170.0;
+/// Between the naive and accurate fountains of a single timestep
+const PAIR_SPACING: f32 = 60.0;
+
+const RENDER_LAYER: usize = 11;
+
+struct Systems;
+
+impl TimesteppedSystems for Systems {
+    fn get_systems_for_timestep<T: TimestepComponent>() -> ScheduleConfigs<ScheduleSystem> {
+        // Particles are emitted already aged by however much of the update they were due for
+        (run::<T>, emit::<T>).chain()
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<EmissionRate>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, update_labels)
+        .add_systems_with_timestep::<Systems>();
+}
+
+fn setup(mut commands: Commands, mut despawns: ResMut<DespawnSystems>) {
+    let despawn = commands.register_system(despawn);
+    despawns.0.push(despawn);
+    let spawn = commands.register_system(spawn);
+
+    let camera = commands.spawn_simulation_camera(RENDER_LAYER).id();
+    commands.insert_resource(FountainMeta { camera, spawn });
+}
+
+fn despawn(mut commands: Commands, entities: Query<Entity, Or<(With<Fountain>, With<Particle>)>>) {
+    for entity in entities.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn spawn(timestep: In<Timestep>, mut commands: Commands) {
+    let colour: Color = timestep.palette().sample_unchecked(0.0).into();
+
+    for accurate in [false, true] {
+        let x =
+            ((timestep.index() as f32) * SPACING) - 280.0 + (accurate as u8 as f32) * PAIR_SPACING;
+        commands.spawn_with_timestep(
+            &timestep.0,
+            (
+                Fountain {
+                    timestep: timestep.0,
+                    accurate,
+                    owed: 0.0,
+                    emitted: 0,
+                    expected: 0.0,
+                },
+                Text2d::default(),
+                TextFont::from_font_size(14.0),
+                TextColor(colour),
+                Anchor::TOP_CENTER,
+                Transform::from_xyz(x, GROUND, 0.0),
+                RenderLayers::layer(RENDER_LAYER),
+                children![(
+                    Transform::from_xyz(0.0, 2.0, 0.0),
+                    RenderLayers::layer(RENDER_LAYER),
+                    Sprite::from_color(colour, Vec2::new(16.0, 4.0)),
+                )],
+            ),
+        );
+    }
+}
+
+/// Spreads the launch angles evenly without any randomness, using the golden ratio
+fn launch_velocity(index: u32) -> Vec2 {
+    let fraction = ((index as f32) * 0.618_034).fract();
+    let angle = ((fraction * 2.0) - 1.0) * SPREAD;
+    Vec2::new(angle.sin(), angle.cos()) * LAUNCH_SPEED
+}
+
+/// Moves a particle forwards by `dt`, with semi-implicit Euler
+fn integrate(position: &mut Vec2, velocity: &mut Vec2, dt: f32) {
+    velocity.y -= GRAVITY * dt;
+    *velocity *= (-DRAG * dt).exp();
+    *position += *velocity * dt;
+}
+
+fn emit<T: TimestepComponent>(
+    mut commands: Commands,
+    mut fountains: Query<(&mut Fountain, &Transform), With<T>>,
+    rate: Res<EmissionRate>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+
+    for (mut fountain, transform) in fountains.iter_mut() {
+        let colour: Color = fountain.timestep.palette().sample_unchecked(0.0).into();
+        fountain.expected += rate.0 * dt;
+
+        let count = if fountain.accurate {
+            fountain.owed += rate.0 * dt;
+            let count = fountain.owed.floor();
+            fountain.owed -= count;
+            count as u32
+        } else {
+            // Drops the fraction every update, and emits every particle of an update from the same point
+            (rate.0 * dt) as u32
+        };
+
+        for i in 0..count {
+            let mut position = transform.translation.truncate();
+            let mut velocity = launch_velocity(fountain.emitted);
+            fountain.emitted += 1;
+
+            // Each particle was due at some point during the update, so catch it up by however long ago that was
+            let age = if fountain.accurate {
+                ((count - i - 1) as f32 + fountain.owed) / rate.0
+            } else {
+                0.0
+            };
+            integrate(&mut position, &mut velocity, age);
+
+            commands.spawn_with_timestep(
+                &fountain.timestep,
+                (
+                    Particle { velocity, age },
+                    SimulationTransform::from_xyz(position.x, position.y, 1.0),
+                    RenderLayers::layer(RENDER_LAYER),
+                    Sprite::from_color(colour, Vec2::splat(PARTICLE_SIZE)),
+                ),
+            );
+        }
+    }
+}
+
+fn run<T: TimestepComponent>(
+    mut commands: Commands,
+    mut particles: Query<(Entity, &mut Particle, &mut SimulationTransform), With<T>>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut particle, mut transform) in particles.iter_mut() {
+        particle.age += dt;
+        if particle.age > LIFETIME {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let mut position = transform.translation.truncate();
+        integrate(&mut position, &mut particle.velocity, dt);
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
+
+fn update_labels(mut fountains: Query<(&mut Text2d, &Fountain)>) {
+    for (mut text, fountain) in fountains.iter_mut() {
+        text.0 = format!(
+            "{}\n{}\n{} of {:.0}",
+            fountain.timestep.name(),
+            if fountain.accurate {
+                "Accurate"
+            } else {
+                "Naive"
+            },
+            fountain.emitted,
+            fountain.expected,
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use bevy::{
+    camera::visibility::RenderLayers,
+    ecs::{
+        schedule::ScheduleConfigs,
+        system::{ScheduleSystem, SystemId},
+    },
+    math::curve::UnevenSampleAutoCurve,
+    prelude::*,
+    sprite::Anchor,
+};
+
+use crate::{
+    configuration::{
+        ActiveTimesteps, AppExt, CommandsExt, DespawnSystems, SimulationMeta, Timestep,
+        TimestepComponent, TimesteppedSystems,
+    },
+    interpolation::SimulationTransform,
+};
+
+#[derive(Resource)]
+pub struct KeyframesMeta {
+    pub camera: Entity,
+    pub spawn: SystemId<In<Timestep>>,
+}
+
+impl SimulationMeta for KeyframesMeta {
+    fn get(&self) -> (Entity, SystemId<In<Timestep>>) {
+        (self.camera, self.spawn)
+    }
+}
+
+/// A looping animation of position within a lane, and rotation in radians
+#[derive(Resource)]
+struct Animation {
+    translation: UnevenSampleAutoCurve<Vec2>,
+    rotation: UnevenSampleAutoCurve<f32>,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        // The last keyframe matches the first, so that the animation loops seamlessly
+        let keyframes = [
+            (0.0, Vec2::new(-250.0, 0.0), 0.0),
+            (0.6, Vec2::new(-80.0, 50.0), 120.0),
+            (1.2, Vec2::new(80.0, -50.0), 240.0),
+            (1.8, Vec2::new(250.0, 0.0), 360.0),
+            (2.4, Vec2::new(0.0, -40.0), 180.0),
+            (DURATION, Vec2::new(-250.0, 0.0), 0.0),
+        ];
+
+        Self {
+            translation: UnevenSampleAutoCurve::new(
+                keyframes.map(|(time, translation, _)| (time, translation)),
+            )
+            .unwrap(),
+            rotation: UnevenSampleAutoCurve::new(
+                keyframes.map(|(time, _, degrees): (f32, Vec2, f32)| (time, degrees.to_radians())),
+            )
+            .unwrap(),
+        }
+    }
+}
+
+impl Animation {
+    fn sample(&self, phase: f32) -> (Vec2, f32) {
+        (
+            self.translation.sample_clamped(phase),
+            self.rotation.sample_clamped(phase),
+        )
+    }
+}
+
+#[derive(Component)]
+struct Animated {
+    timestep: Timestep,
+    /// Centre of the lane that the animation plays in
+    origin: Vec2,
+    /// Seconds into the animation, accumulated from each update's delta time as gameplay code would
+    phase: f32,
+    /// Virtual time when spawned, that the reference playback is measured from
+    start: f64,
+}
+
+#[derive(Component)]
+struct AnimatedLabel(Entity);
+
+#[derive(GizmoConfigGroup, Default, Reflect)]
+struct KeyframeGizmos;
+
+/// Seconds per loop of the animation
+const DURATION: f32 = 3.0;
+const SIZE: Vec2 = Vec2::new(40.0, 20.0);
+const LANE_HEIGHT: f32 = 160.0;
+
+const RENDER_LAYER: usize = 15;
+
+struct Systems;
+
+impl TimesteppedSystems for Systems {
+    fn get_systems_for_timestep<T: TimestepComponent>() -> ScheduleConfigs<ScheduleSystem> {
+        run::<T>.into_configs()
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<Animation>()
+        .init_gizmo_group::<KeyframeGizmos>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, update_labels)
+        .add_systems(PostUpdate, draw)
+        .add_systems_with_timestep::<Systems>();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut despawns: ResMut<DespawnSystems>,
+    mut gizmos: ResMut<GizmoConfigStore>,
+) {
+    let despawn = commands.register_system(despawn);
+    despawns.0.push(despawn);
+    let spawn = commands.register_system(spawn);
+
+    let camera = commands.spawn_simulation_camera(RENDER_LAYER).id();
+    commands.insert_resource(KeyframesMeta { camera, spawn });
+
+    gizmos.config_mut::<KeyframeGizmos>().0.render_layers = RenderLayers::layer(RENDER_LAYER);
+}
+
+fn despawn(
+    mut commands: Commands,
+    entities: Query<Entity, Or<(With<Animated>, With<AnimatedLabel>)>>,
+) {
+    for entity in entities.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn spawn(
+    timestep: In<Timestep>,
+    mut commands: Commands,
+    active_timesteps: Res<ActiveTimesteps>,
+    animation: Res<Animation>,
+    time: Res<Time<Virtual>>,
+) {
+    let colour: Color = timestep.palette().sample_unchecked(0.0).into();
+
+    let origin = Vec2::new(0.0, active_timesteps.lane_centre(timestep.0, LANE_HEIGHT));
+
+    let (translation, angle) = animation.sample(0.0);
+    let position = origin + translation;
+    let mut transform = SimulationTransform::from_xyz(position.x, position.y, 1.0);
+    transform.rotation = Quat::from_rotation_z(angle);
+
+    let animated = commands
+        .spawn_with_timestep(
+            &timestep.0,
+            (
+                Animated {
+                    timestep: timestep.0,
+                    origin,
+                    phase: 0.0,
+                    start: time.elapsed_secs_f64(),
+                },
+                transform,
+                RenderLayers::layer(RENDER_LAYER),
+                Sprite::from_color(colour, SIZE),
+            ),
+        )
+        .id();
+
+    commands.spawn((
+        AnimatedLabel(animated),
+        Text2d::default(),
+        TextFont::from_font_size(16.0),
+        TextColor(colour),
+        Anchor::TOP_LEFT,
+        Transform::from_xyz(-300.0, origin.y + (LANE_HEIGHT / 2.0) - 8.0, 0.0),
+        RenderLayers::layer(RENDER_LAYER),
+    ));
+}
+
+fn run<T: TimestepComponent>(
+    mut animated: Query<(&mut Animated, &mut SimulationTransform), With<T>>,
+    animation: Res<Animation>,
+    time: Res<Time>,
+) {
+    for (mut animated, mut transform) in animated.iter_mut() {
+        animated.phase = (animated.phase + time.delta_secs()) % DURATION;
+
+        let (translation, angle) = animation.sample(animated.phase);
+        transform.translation = (animated.origin + translation).extend(transform.translation.z);
+        transform.rotation = Quat::from_rotation_z(angle);
+    }
+}
+
+/// Where the animation should be, sampled from virtual time on every render frame
+fn reference_phase(animated: &Animated, time: &Time<Virtual>) -> f32 {
+    ((time.elapsed_secs_f64() - animated.start) % (DURATION as f64)) as f32
+}
+
+fn draw(
+    animated: Query<&Animated>,
+    animation: Res<Animation>,
+    time: Res<Time<Virtual>>,
+    mut gizmos: Gizmos<KeyframeGizmos>,
+) {
+    for animated in animated.iter() {
+        let colour = animated.timestep.palette().sample_unchecked(0.0);
+
+        let path = (0..=64).map(|i| {
+            let phase = DURATION * (i as f32) / 64.0;
+            animated.origin + animation.sample(phase).0
+        });
+        gizmos.linestrip_2d(path, Color::WHITE.with_alpha(0.25));
+
+        let (translation, angle) = animation.sample(reference_phase(animated, &time));
+        gizmos.rect_2d(
+            Isometry2d::new(animated.origin + translation, Rot2::radians(angle)),
+            SIZE + 8.0,
+            colour.with_alpha(0.5),
+        );
+    }
+}
+
+fn update_labels(
+    mut labels: Query<(&mut Text2d, &AnimatedLabel)>,
+    animated: Query<&Animated>,
+    time: Res<Time<Virtual>>,
+) {
+    for (mut text, label) in labels.iter_mut() {
+        let Ok(animated) = animated.get(label.0) else {
+            continue;
+        };
+
+        // Wrapped to the nearest loop, so that being just ahead doesn't read as nearly a whole loop behind
+        let drift = animated.phase - reference_phase(animated, &time);
+        let drift = drift - (DURATION * (drift / DURATION).round());
+        text.0 = format!(
+            "{}\nPhase drift: {:+.1} ms",
+            animated.timestep.name(),
+            drift * 1000.0,
+        );
+    }
+}
@@ -1,5 +1,8 @@
+use std::{collections::VecDeque, ops::RangeInclusive};
+
 use bevy::{
-    camera::visibility::RenderLayers,
+    asset::RenderAssetUsages,
+    camera::visibility::{NoFrustumCulling, RenderLayers},
     color::ColorCurve,
     core_pipeline::tonemapping::Tonemapping,
     ecs::{
@@ -7,8 +10,11 @@ use bevy::{
         system::{ScheduleSystem, SystemId},
     },
     math::DVec3,
+    mesh::{PrimitiveTopology, VertexAttributeValues},
     prelude::*,
+    time::common_conditions::paused,
 };
+use serde::{Deserialize, Serialize};
 
 use crate::configuration::{
     AppExt, CommandsExt, DespawnSystems, SimulationMeta, Timestep, TimesteppedSystems,
@@ -26,29 +32,269 @@ impl SimulationMeta for LorenzAttractorMeta {
     }
 }
 
-#[derive(Resource)]
-struct Parameters {
+/// Parameters and initial conditions of the Lorenz system
+#[derive(Resource, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LorenzConfig {
     /// σ
-    sigma: f64,
+    pub sigma: f64,
     /// ρ
-    rho: f64,
+    pub rho: f64,
     /// β
-    beta: f64,
+    pub beta: f64,
+    /// Where every trajectory starts
+    pub start: DVec3,
+    /// Whether each timestep starts slightly apart from the others, to show sensitivity to initial conditions
+    pub offset_starts: bool,
+    pub reference: DivergenceReference,
+    /// Line segments kept in each trajectory's trail
+    pub trail_length: u32,
+}
+
+impl LorenzConfig {
+    /// Rate of change at the given point, with the z axis of the usual formulation pointing up along y
+    fn velocity(&self, position: DVec3) -> DVec3 {
+        let Self {
+            sigma, rho, beta, ..
+        } = *self;
+        DVec3::new(
+            position.z * (rho - position.y) - position.x,
+            position.z * position.x - beta * position.y,
+            sigma * (position.x - position.z),
+        )
+    }
+}
+
+impl Default for LorenzConfig {
+    fn default() -> Self {
+        Self {
+            sigma: 10.0,
+            rho: 28.0,
+            beta: 8.0 / 3.0,
+            start: DVec3::new(2.0, 1.0, 1.0),
+            offset_starts: false,
+            reference: default(),
+            trail_length: 20_000,
+        }
+    }
+}
+
+pub const MIN_SIGMA: f32 = 0.0;
+pub const MAX_SIGMA: f32 = 30.0;
+pub const MIN_RHO: f32 = 0.0;
+pub const MAX_RHO: f32 = 200.0;
+pub const MIN_BETA: f32 = 0.0;
+pub const MAX_BETA: f32 = 10.0;
+pub const MIN_TRAIL_LENGTH: u32 = 100;
+pub const MAX_TRAIL_LENGTH: u32 = 200_000;
+/// Each coordinate of the starting point is within this distance of the origin
+pub const MAX_START: f32 = 50.0;
+
+/// Well-known regimes of the Lorenz system, as (name, σ, ρ, β)
+pub const LORENZ_PRESETS: [(&str, f64, f64, f64); 5] = [
+    ("Chaotic", 10.0, 28.0, 8.0 / 3.0),
+    ("Stable Spirals", 10.0, 14.0, 8.0 / 3.0),
+    ("Transient Chaos", 10.0, 21.0, 8.0 / 3.0),
+    ("Periodic Window", 10.0, 99.96, 8.0 / 3.0),
+    ("Intermittency", 10.0, 166.1, 8.0 / 3.0),
+];
+
+/// What each trajectory's divergence is measured against
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum DivergenceReference {
+    /// A separate trajectory, integrated with a tiny fixed delta and advanced by virtual time
+    #[default]
+    HighRate,
+    /// The trajectory of one of the timesteps
+    Timestep(Timestep),
+}
+
+/// How far each active timestep's trajectory has drifted from the [`DivergenceReference`].
+///
+/// Measured once per frame, at the same moment of virtual time for every trajectory,
+/// so that a timestep running ahead of or behind virtual time also counts as divergence.
+#[derive(Resource, Default)]
+pub struct LorenzDivergence {
+    /// Seconds of virtual time since the trajectories were spawned
+    pub elapsed: f64,
+    pub timesteps: Vec<TimestepDivergence>,
+    /// Position of the high-rate reference trajectory
+    reference: DVec3,
+}
+
+pub struct TimestepDivergence {
+    pub timestep: Timestep,
+    /// Elapsed time and distance from the reference for each frame, over the last [`DIVERGENCE_WINDOW`] seconds
+    pub samples: VecDeque<(f64, f64)>,
+    /// Least-squares fit of the distance's logarithm against time, until the distance saturates
+    fit: LinearFit,
+    /// Whether the distance has grown past [`FIT_RANGE`], after which it only wanders around the size of the attractor
+    saturated: bool,
+}
+
+impl TimestepDivergence {
+    fn new(timestep: Timestep) -> Self {
+        Self {
+            timestep,
+            samples: VecDeque::new(),
+            fit: LinearFit::default(),
+            saturated: false,
+        }
+    }
+
+    fn record(&mut self, elapsed: f64, distance: f64) {
+        self.samples.push_back((elapsed, distance));
+        while self
+            .samples
+            .front()
+            .is_some_and(|&(time, _)| time < elapsed - DIVERGENCE_WINDOW)
+        {
+            self.samples.pop_front();
+        }
+
+        // Stop fitting for good once saturated, as later dips back into range aren't exponential growth
+        if distance > *FIT_RANGE.end() {
+            self.saturated = true;
+        }
+        if !self.saturated && FIT_RANGE.contains(&distance) {
+            self.fit.add(elapsed, distance.ln());
+        }
+    }
+
+    /// Estimate of the largest Lyapunov exponent, from how quickly the distance grew before saturating.
+    ///
+    /// `None` until enough growth has been seen to fit to.
+    pub fn lyapunov_exponent(&self) -> Option<f64> {
+        self.fit.slope()
+    }
+}
+
+#[derive(Default)]
+struct LinearFit {
+    count: f64,
+    sum_x: f64,
+    sum_y: f64,
+    sum_xy: f64,
+    sum_xx: f64,
+    first_x: Option<f64>,
+    last_x: f64,
+}
+
+impl LinearFit {
+    fn add(&mut self, x: f64, y: f64) {
+        self.count += 1.0;
+        self.sum_x += x;
+        self.sum_y += y;
+        self.sum_xy += x * y;
+        self.sum_xx += x * x;
+        self.first_x.get_or_insert(x);
+        self.last_x = x;
+    }
+
+    fn slope(&self) -> Option<f64> {
+        let first_x = self.first_x?;
+        if self.last_x - first_x < MIN_FIT_DURATION {
+            return None;
+        }
+
+        let covariance = (self.count * self.sum_xy) - (self.sum_x * self.sum_y);
+        let variance = (self.count * self.sum_xx) - (self.sum_x * self.sum_x);
+        Some(covariance / variance)
+    }
 }
 
+/// Delta time of the high-rate reference trajectory
+const REFERENCE_DELTA: f64 = 1.0 / 4096.0;
+/// Seconds of divergence history kept for graphing
+pub const DIVERGENCE_WINDOW: f64 = 30.0;
+/// Distances fitted to for the Lyapunov exponent, until the distance first grows past them.
+/// Smaller distances are lost in rounding error, and larger ones are capped by the size of the attractor
+const FIT_RANGE: RangeInclusive<f64> = 1e-9..=1.0;
+/// Seconds of growth needed before estimating the Lyapunov exponent
+const MIN_FIT_DURATION: f64 = 2.0;
+
+/// How far apart each timestep starts when [`LorenzConfig::offset_starts`] is enabled
+const START_OFFSET: f64 = 1e-6;
+
+#[derive(Component)]
+struct Trajectory {
+    timestep: Timestep,
+    position: DVec3,
+}
+
+/// Segments in each of a trail's meshes
+const TRAIL_CHUNK_LENGTH: usize = 4096;
+
+/// The most recent segments of a trajectory, kept as a ring buffer in the vertices of line list meshes.
+///
+/// The ring buffer is split into chunks of [`TRAIL_CHUNK_LENGTH`] segments, each with its own mesh.
+/// Each update only overwrites a single segment, so only the chunk holding it is re-uploaded,
+/// however long the trail is.
 #[derive(Component)]
-#[require(Points)]
-struct Trajectory(DVec3);
+struct Trail {
+    /// Segment that the next update overwrites
+    head: usize,
+    length: usize,
+    chunks: Vec<Handle<Mesh>>,
+    /// End of the previous segment, that the next one starts from
+    previous: Option<(Vec3, [f32; 4])>,
+}
+
+impl Trail {
+    fn new(length: usize, meshes: &mut Assets<Mesh>) -> Self {
+        let chunks = (0..length)
+            .step_by(TRAIL_CHUNK_LENGTH)
+            .map(|start| meshes.add(Self::chunk_mesh((length - start).min(TRAIL_CHUNK_LENGTH))))
+            .collect();
+
+        Self {
+            head: 0,
+            length,
+            chunks,
+            previous: None,
+        }
+    }
+
+    /// A mesh to hold part of the trail, with every segment collapsed onto the origin until it's written to
+    fn chunk_mesh(segments: usize) -> Mesh {
+        Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::default())
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0; 3]; segments * 2])
+            .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, vec![[0.0; 4]; segments * 2])
+    }
+
+    fn push(&mut self, meshes: &mut Assets<Mesh>, point: Vec3, colour: Oklaba) {
+        let colour = LinearRgba::from(colour).to_f32_array();
+        let Some((previous, previous_colour)) = self.previous.replace((point, colour)) else {
+            return;
+        };
 
-#[derive(Component, Default)]
-struct Points(Vec<Vec3>);
+        if let Some(mesh) = meshes.get_mut(&self.chunks[self.head / TRAIL_CHUNK_LENGTH]) {
+            let start = (self.head % TRAIL_CHUNK_LENGTH) * 2;
+            if let Some(VertexAttributeValues::Float32x3(positions)) =
+                mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+            {
+                positions[start] = previous.to_array();
+                positions[start + 1] = point.to_array();
+            }
+            if let Some(VertexAttributeValues::Float32x4(colours)) =
+                mesh.attribute_mut(Mesh::ATTRIBUTE_COLOR)
+            {
+                colours[start] = previous_colour;
+                colours[start + 1] = colour;
+            }
+        }
+        self.head = (self.head + 1) % self.length;
+    }
+}
+
+#[derive(Resource)]
+struct TrailMaterial(Handle<StandardMaterial>);
 
 #[derive(Component)]
 struct Colours {
     curve: ColorCurve<Oklaba>,
     interp_seconds: f32,
     factor: f32,
-    seq: Vec<Oklaba>,
 }
 
 impl Colours {
@@ -57,7 +303,6 @@ impl Colours {
             curve,
             interp_seconds,
             factor: 0.0,
-            seq: Vec::new(),
         }
     }
 
@@ -66,13 +311,12 @@ impl Colours {
         Self::new(timestep.palette(), INTERP_SECONDS)
     }
 
-    fn push_next(&mut self, dt: f32) {
+    fn next(&mut self, dt: f32) -> Oklaba {
         self.factor += (self.curve.domain().length() / self.interp_seconds) * dt;
         if !self.curve.domain().contains(self.factor) {
             self.factor = self.curve.domain().start();
         }
-        let colour = self.curve.sample_unchecked(self.factor);
-        self.seq.push(colour);
+        self.curve.sample_unchecked(self.factor)
     }
 }
 
@@ -87,11 +331,19 @@ impl TimesteppedSystems for Systems {
 }
 
 pub fn plugin(app: &mut App) {
-    app.add_systems(Startup, setup)
+    app.init_resource::<LorenzConfig>()
+        .init_resource::<LorenzDivergence>()
+        .add_systems(Startup, setup)
+        // Measured after every timestep has updated, and frozen while paused so that the graph can be inspected
+        .add_systems(PostUpdate, measure_divergence.run_if(not(paused)))
         .add_systems_with_timestep::<Systems>();
 }
 
-fn setup(mut commands: Commands, mut despawns: ResMut<DespawnSystems>) {
+fn setup(
+    mut commands: Commands,
+    mut despawns: ResMut<DespawnSystems>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
     let despawn = commands.register_system(despawn);
     despawns.0.push(despawn);
     let spawn = commands.register_system(spawn);
@@ -112,49 +364,131 @@ fn setup(mut commands: Commands, mut despawns: ResMut<DespawnSystems>) {
 
     commands.insert_resource(LorenzAttractorMeta { camera, spawn });
 
-    commands.insert_resource(Parameters {
-        sigma: 10.0,
-        rho: 28.0,
-        beta: 8.0 / 3.0,
+    // Coloured by the trail's vertices
+    let material = materials.add(StandardMaterial {
+        base_color: Color::WHITE,
+        unlit: true,
+        ..default()
     });
+    commands.insert_resource(TrailMaterial(material));
 }
 
-fn despawn(mut commands: Commands, trajectories: Query<Entity, With<Trajectory>>) {
+fn despawn(
+    mut commands: Commands,
+    trajectories: Query<Entity, With<Trajectory>>,
+    mut divergence: ResMut<LorenzDivergence>,
+) {
     for entity in trajectories.iter() {
         commands.entity(entity).despawn();
     }
+    *divergence = default();
 }
 
-fn spawn(timestep: In<Timestep>, mut commands: Commands) {
+fn spawn(
+    timestep: In<Timestep>,
+    mut commands: Commands,
+    config: Res<LorenzConfig>,
+    mut divergence: ResMut<LorenzDivergence>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    material: Res<TrailMaterial>,
+) {
+    divergence.reference = config.start;
+    divergence
+        .timesteps
+        .push(TimestepDivergence::new(timestep.0));
+
+    let mut start = config.start;
+    if config.offset_starts {
+        start.x += START_OFFSET * (timestep.index() as f64);
+    }
+
+    // An empty trail would have nowhere to write its segments
+    let length = config
+        .trail_length
+        .clamp(MIN_TRAIL_LENGTH, MAX_TRAIL_LENGTH);
+    let trail = Trail::new(length as usize, &mut meshes);
+    let chunks = trail
+        .chunks
+        .iter()
+        .map(|mesh| {
+            (
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(material.0.clone()),
+                // The bounds would only be calculated from the empty trail
+                NoFrustumCulling,
+                RenderLayers::layer(RENDER_LAYER),
+            )
+        })
+        .collect::<Vec<_>>();
+
     commands.spawn_with_timestep(
         &timestep.0,
         (
-            Trajectory(DVec3::new(2.0, 1.0, 1.0)),
-            RenderLayers::layer(RENDER_LAYER),
+            Trajectory {
+                timestep: timestep.0,
+                position: start,
+            },
+            trail,
+            Transform::default(),
+            Visibility::default(),
             Colours::from_timestep(&timestep.0),
+            Children::spawn(SpawnIter(chunks.into_iter())),
         ),
     );
 }
 
 fn run<T: Component>(
-    mut trajectories: Query<(&mut Trajectory, &mut Points, &mut Colours), With<T>>,
-    mut gizmos: Gizmos,
-    parameters: Res<Parameters>,
+    mut trajectories: Query<(&mut Trajectory, &mut Trail, &mut Colours), With<T>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    config: Res<LorenzConfig>,
     time: Res<Time>,
 ) {
-    let Parameters { sigma, rho, beta } = *parameters;
-    for (mut trajectory, mut points, mut colours) in trajectories.iter_mut() {
-        let pos = &mut trajectory.0;
-        let delta = DVec3::new(
-            pos.z * (rho - pos.y) - pos.x,
-            pos.z * pos.x - beta * pos.y,
-            sigma * (pos.x - pos.z),
-        );
-        *pos += delta * time.delta_secs_f64();
-
-        let pos = pos.as_vec3();
-        points.0.push(pos);
-        colours.push_next(time.delta_secs());
-        gizmos.linestrip_gradient(points.0.iter().copied().zip(colours.seq.iter().copied()));
+    for (mut trajectory, mut trail, mut colours) in trajectories.iter_mut() {
+        let pos = &mut trajectory.position;
+        *pos += config.velocity(*pos) * time.delta_secs_f64();
+
+        let colour = colours.next(time.delta_secs());
+        trail.push(&mut meshes, pos.as_vec3(), colour);
+    }
+}
+
+fn measure_divergence(
+    trajectories: Query<&Trajectory>,
+    mut divergence: ResMut<LorenzDivergence>,
+    config: Res<LorenzConfig>,
+    time: Res<Time<Virtual>>,
+) {
+    if divergence.timesteps.is_empty() {
+        return;
+    }
+    let divergence = &mut *divergence;
+
+    // Split the frame's delta into equal steps, so that the reference lands exactly on the current virtual time
+    let steps = (time.delta_secs_f64() / REFERENCE_DELTA).ceil();
+    let dt = time.delta_secs_f64() / steps;
+    for _ in 0..(steps as u32) {
+        divergence.reference += config.velocity(divergence.reference) * dt;
+    }
+    divergence.elapsed += time.delta_secs_f64();
+
+    let reference = match config.reference {
+        DivergenceReference::HighRate => Some(divergence.reference),
+        DivergenceReference::Timestep(timestep) => trajectories
+            .iter()
+            .find(|trajectory| trajectory.timestep == timestep)
+            .map(|trajectory| trajectory.position),
+    };
+    let Some(reference) = reference else {
+        return;
+    };
+
+    for entry in &mut divergence.timesteps {
+        let Some(trajectory) = trajectories
+            .iter()
+            .find(|trajectory| trajectory.timestep == entry.timestep)
+        else {
+            continue;
+        };
+        entry.record(divergence.elapsed, trajectory.position.distance(reference));
     }
 }
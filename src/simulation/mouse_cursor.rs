@@ -131,12 +131,15 @@ fn spawn(
 fn move_cursor<T: Component>(
     mut cursor: Single<&mut SimulationTransform, (With<Cursor>, With<T>)>,
     window: Single<&Window, With<PrimaryWindow>>,
-    camera: Single<(&Camera, &GlobalTransform), With<Camera2d>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    meta: Res<MouseCursorMeta>,
 ) {
     let Some(position) = window.cursor_position() else {
         return;
     };
 
-    let position = camera.0.viewport_to_world_2d(camera.1, position).unwrap();
+    // Other simulations have 2D cameras too, so use this simulation's own orthographic camera
+    let (camera, transform) = cameras.get(meta.camera).unwrap();
+    let position = camera.viewport_to_world_2d(transform, position).unwrap();
     cursor.translation = position.extend(-1.0);
 }
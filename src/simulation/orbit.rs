@@ -0,0 +1,341 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    camera::visibility::RenderLayers,
+    ecs::{
+        schedule::ScheduleConfigs,
+        system::{ScheduleSystem, SystemId},
+    },
+    prelude::*,
+    sprite::Anchor,
+    time::common_conditions::paused,
+};
+
+use crate::{
+    configuration::{
+        AppExt, CommandsExt, DespawnSystems, SimulationMeta, Timestep, TimestepComponent,
+        TimesteppedSystems,
+    },
+    interpolation::SimulationTransform,
+};
+
+#[derive(Resource)]
+pub struct OrbitMeta {
+    pub camera: Entity,
+    pub spawn: SystemId<In<Timestep>>,
+}
+
+impl SimulationMeta for OrbitMeta {
+    fn get(&self) -> (Entity, SystemId<In<Timestep>>) {
+        (self.camera, self.spawn)
+    }
+}
+
+/// A star and a planet orbiting their shared centre of mass
+#[derive(Component)]
+struct Orbit {
+    timestep: Timestep,
+    /// The star, then the planet
+    bodies: [Entity; 2],
+    initial_energy: f32,
+    initial_momentum: f32,
+    initial_periapsis: f32,
+}
+
+#[derive(Component)]
+struct Body {
+    mass: f32,
+    velocity: Vec2,
+}
+
+/// Drift of the orbit over time, relative to its initial state
+#[derive(Component, Default)]
+struct History {
+    /// Fraction of the initial energy
+    energy: VecDeque<f32>,
+    /// Fraction of the initial angular momentum
+    momentum: VecDeque<f32>,
+    /// Rotation of the periapsis, in degrees
+    precession: VecDeque<f32>,
+    /// Trails of the star and the planet
+    trails: [VecDeque<Vec2>; 2],
+}
+
+#[derive(GizmoConfigGroup, Default, Reflect)]
+struct OrbitGizmos;
+
+const G: f32 = 4_000_000.0;
+const STAR_MASS: f32 = 1.0;
+/// Heavy enough for the star to visibly wobble
+const PLANET_MASS: f32 = 0.25;
+/// Starting distance between the star and the planet, at the furthest point of the orbit
+const APOAPSIS: f32 = 250.0;
+const ECCENTRICITY: f32 = 0.7;
+
+/// Fraction of the initial energy shown at the top and bottom of its graph
+const ENERGY_RANGE: f32 = 0.1;
+/// Fraction of the initial angular momentum shown at the top and bottom of its graph
+const MOMENTUM_RANGE: f32 = 0.1;
+/// Degrees of precession shown at the top and bottom of its graph
+const PRECESSION_RANGE: f32 = 30.0;
+const GRAPH_SIZE: Vec2 = Vec2::new(300.0, 120.0);
+const ENERGY_GRAPH: Vec2 = Vec2::new(120.0, 180.0);
+const MOMENTUM_GRAPH: Vec2 = Vec2::new(120.0, 0.0);
+const PRECESSION_GRAPH: Vec2 = Vec2::new(120.0, -180.0);
+/// Number of frames shown in each graph
+const HISTORY_LENGTH: usize = 600;
+const TRAIL_LENGTH: usize = 200;
+
+const RENDER_LAYER: usize = 6;
+
+struct Systems;
+
+impl TimesteppedSystems for Systems {
+    fn get_systems_for_timestep<T: TimestepComponent>() -> ScheduleConfigs<ScheduleSystem> {
+        run::<T>.into_configs()
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_gizmo_group::<OrbitGizmos>()
+        .add_systems(Startup, setup)
+        // Freeze the graphs while paused, so that they can be inspected
+        .add_systems(Update, record_history.run_if(not(paused)))
+        .add_systems(PostUpdate, draw.after(TransformSystems::Propagate))
+        .add_systems_with_timestep::<Systems>();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut despawns: ResMut<DespawnSystems>,
+    mut gizmos: ResMut<GizmoConfigStore>,
+) {
+    let despawn = commands.register_system(despawn);
+    despawns.0.push(despawn);
+    let spawn = commands.register_system(spawn);
+
+    let camera = commands.spawn_simulation_camera(RENDER_LAYER).id();
+    commands.insert_resource(OrbitMeta { camera, spawn });
+
+    gizmos.config_mut::<OrbitGizmos>().0.render_layers = RenderLayers::layer(RENDER_LAYER);
+
+    for (position, title) in [
+        (
+            ENERGY_GRAPH,
+            format!("Energy (±{:.0}%)", ENERGY_RANGE * 100.0),
+        ),
+        (
+            MOMENTUM_GRAPH,
+            format!("Angular Momentum (±{:.0}%)", MOMENTUM_RANGE * 100.0),
+        ),
+        (
+            PRECESSION_GRAPH,
+            format!("Periapsis Precession (±{PRECESSION_RANGE:.0}°)"),
+        ),
+    ] {
+        commands.spawn((
+            Text2d::new(title),
+            TextFont::from_font_size(16.0),
+            Anchor::BOTTOM_LEFT,
+            Transform::from_translation(
+                (position + Vec2::new(0.0, GRAPH_SIZE.y + 4.0)).extend(0.0),
+            ),
+            RenderLayers::layer(RENDER_LAYER),
+        ));
+    }
+}
+
+fn despawn(mut commands: Commands, entities: Query<Entity, Or<(With<Orbit>, With<Body>)>>) {
+    for entity in entities.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Positions and velocities of the star and the planet
+type State = [(Vec2, Vec2); 2];
+
+fn energy([(star, star_velocity), (planet, planet_velocity)]: State) -> f32 {
+    let kinetic = (STAR_MASS * star_velocity.length_squared() / 2.0)
+        + (PLANET_MASS * planet_velocity.length_squared() / 2.0);
+    kinetic - (G * STAR_MASS * PLANET_MASS / star.distance(planet))
+}
+
+fn angular_momentum([(star, star_velocity), (planet, planet_velocity)]: State) -> f32 {
+    (STAR_MASS * star.perp_dot(star_velocity)) + (PLANET_MASS * planet.perp_dot(planet_velocity))
+}
+
+/// Direction of the periapsis in radians, from the Laplace-Runge-Lenz vector of the planet relative to the star
+fn periapsis([(star, star_velocity), (planet, planet_velocity)]: State) -> f32 {
+    let position = planet - star;
+    let velocity = planet_velocity - star_velocity;
+    let angular_momentum = position.perp_dot(velocity);
+    let lrl = (velocity.perp() * -angular_momentum)
+        - (position.normalize_or_zero() * G * (STAR_MASS + PLANET_MASS));
+    lrl.to_angle()
+}
+
+fn spawn(timestep: In<Timestep>, mut commands: Commands) {
+    let palette = timestep.palette();
+
+    // Relative speed at apoapsis, from the vis-viva equation
+    let speed = (G * (STAR_MASS + PLANET_MASS) * (1.0 - ECCENTRICITY) / APOAPSIS).sqrt();
+    // Split the separation and relative velocity by mass, so that the centre of mass starts at rest on the origin
+    let star_share = PLANET_MASS / (STAR_MASS + PLANET_MASS);
+    let planet_share = STAR_MASS / (STAR_MASS + PLANET_MASS);
+    let state = [
+        (
+            Vec2::new(APOAPSIS * star_share, 0.0),
+            Vec2::new(0.0, speed * star_share),
+        ),
+        (
+            Vec2::new(-APOAPSIS * planet_share, 0.0),
+            Vec2::new(0.0, -speed * planet_share),
+        ),
+    ];
+
+    let bodies = [(STAR_MASS, 1.0, 16.0), (PLANET_MASS, 0.0, 8.0)]
+        .into_iter()
+        .zip(state)
+        .map(|((mass, colour, size), (position, velocity))| {
+            let colour: Color = palette.sample_unchecked(colour).into();
+            commands
+                .spawn_with_timestep(
+                    &timestep.0,
+                    (
+                        Body { mass, velocity },
+                        SimulationTransform::from_xyz(position.x, position.y, 1.0),
+                        RenderLayers::layer(RENDER_LAYER),
+                        Sprite::from_color(colour, Vec2::splat(size)),
+                    ),
+                )
+                .id()
+        })
+        .collect::<Vec<_>>();
+
+    commands.spawn_with_timestep(
+        &timestep.0,
+        (
+            Orbit {
+                timestep: timestep.0,
+                bodies: bodies.try_into().unwrap(),
+                initial_energy: energy(state),
+                initial_momentum: angular_momentum(state),
+                initial_periapsis: periapsis(state),
+            },
+            History::default(),
+        ),
+    );
+}
+
+fn state(bodies: [(&Body, &SimulationTransform); 2]) -> State {
+    bodies.map(|(body, transform)| (transform.translation.truncate(), body.velocity))
+}
+
+fn run<T: TimestepComponent>(
+    orbits: Query<&Orbit, With<T>>,
+    mut bodies: Query<(&mut Body, &mut SimulationTransform), With<T>>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+
+    for orbit in orbits.iter() {
+        let Ok([mut star, mut planet]) = bodies.get_many_mut(orbit.bodies) else {
+            continue;
+        };
+
+        let offset = planet.1.translation.truncate() - star.1.translation.truncate();
+        let force =
+            offset.normalize_or_zero() * G * star.0.mass * planet.0.mass / offset.length_squared();
+
+        // Semi-implicit Euler, which keeps energy bounded with a constant delta time, but not with a variable one.
+        // The forces are equal and opposite, so angular momentum is conserved exactly either way, but the orbit still precesses.
+        for ((body, transform), force) in [(&mut star, force), (&mut planet, -force)] {
+            let acceleration = force / body.mass;
+            body.velocity += acceleration * dt;
+            transform.translation += (body.velocity * dt).extend(0.0);
+        }
+    }
+}
+
+fn record_history(
+    mut orbits: Query<(&Orbit, &mut History)>,
+    bodies: Query<(&Body, &SimulationTransform, &Transform)>,
+) {
+    for (orbit, mut history) in orbits.iter_mut() {
+        let Ok(bodies) = bodies.get_many(orbit.bodies) else {
+            continue;
+        };
+        let state = state(bodies.map(|(body, simulation, _)| (body, simulation)));
+
+        let precession = (periapsis(state) - orbit.initial_periapsis)
+            .to_degrees()
+            .rem_euclid(360.0);
+        // Wrap into ±180°, so that precession in either direction is shown
+        let precession = if precession > 180.0 {
+            precession - 360.0
+        } else {
+            precession
+        };
+
+        let history = &mut *history;
+        for (values, value) in [
+            (
+                &mut history.energy,
+                (energy(state) - orbit.initial_energy) / orbit.initial_energy.abs(),
+            ),
+            (
+                &mut history.momentum,
+                (angular_momentum(state) - orbit.initial_momentum) / orbit.initial_momentum.abs(),
+            ),
+            (&mut history.precession, precession),
+        ] {
+            values.push_back(value);
+            if values.len() > HISTORY_LENGTH {
+                values.pop_front();
+            }
+        }
+
+        for (trail, (_, _, render)) in history.trails.iter_mut().zip(bodies) {
+            trail.push_back(render.translation.truncate());
+            if trail.len() > TRAIL_LENGTH {
+                trail.pop_front();
+            }
+        }
+    }
+}
+
+fn draw(orbits: Query<(&Orbit, &History)>, mut gizmos: Gizmos<OrbitGizmos>) {
+    for origin in [ENERGY_GRAPH, MOMENTUM_GRAPH, PRECESSION_GRAPH] {
+        gizmos.rect_2d(
+            origin + (GRAPH_SIZE / 2.0),
+            GRAPH_SIZE,
+            Color::WHITE.with_alpha(0.5),
+        );
+        gizmos.line_2d(
+            origin + Vec2::new(0.0, GRAPH_SIZE.y / 2.0),
+            origin + Vec2::new(GRAPH_SIZE.x, GRAPH_SIZE.y / 2.0),
+            Color::WHITE.with_alpha(0.25),
+        );
+    }
+
+    for (orbit, history) in orbits.iter() {
+        let colour = orbit.timestep.palette().sample_unchecked(0.0);
+
+        for trail in &history.trails {
+            gizmos.linestrip_2d(trail.iter().copied(), colour.with_alpha(0.5));
+        }
+
+        for (origin, values, range) in [
+            (ENERGY_GRAPH, &history.energy, ENERGY_RANGE),
+            (MOMENTUM_GRAPH, &history.momentum, MOMENTUM_RANGE),
+            (PRECESSION_GRAPH, &history.precession, PRECESSION_RANGE),
+        ] {
+            let points = values.iter().enumerate().map(|(i, &value)| {
+                let x = (i as f32) / (HISTORY_LENGTH as f32);
+                let y = ((value / range).clamp(-1.0, 1.0) + 1.0) / 2.0;
+                origin + (Vec2::new(x, y) * GRAPH_SIZE)
+            });
+            gizmos.linestrip_2d(points, colour);
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    camera::visibility::RenderLayers,
+    ecs::{
+        schedule::ScheduleConfigs,
+        system::{ScheduleSystem, SystemId},
+    },
+    prelude::*,
+    sprite::Anchor,
+};
+
+use crate::{
+    configuration::{
+        AppExt, CommandsExt, DespawnSystems, SimulationMeta, Timestep, TimestepComponent,
+        TimesteppedSystems,
+    },
+    interpolation::SimulationTransform,
+};
+
+#[derive(Resource)]
+pub struct ProjectilesMeta {
+    pub camera: Entity,
+    pub spawn: SystemId<In<Timestep>>,
+}
+
+impl SimulationMeta for ProjectilesMeta {
+    fn get(&self) -> (Entity, SystemId<In<Timestep>>) {
+        (self.camera, self.spawn)
+    }
+}
+
+#[derive(Component)]
+struct Projectile {
+    timestep: Timestep,
+    velocity: Vec2,
+    /// Highest point of the current shot, above the launcher
+    apex: f32,
+    /// Apex height and landing distance of the previous shots
+    shots: VecDeque<Vec2>,
+}
+
+#[derive(Component)]
+struct ShotsLabel(Entity);
+
+#[derive(GizmoConfigGroup, Default, Reflect)]
+struct ProjectileGizmos;
+
+const LAUNCHER: Vec2 = Vec2::new(-150.0, -300.0);
+const LAUNCH_VELOCITY: Vec2 = Vec2::new(300.0, 800.0);
+const GRAVITY: f32 = 2000.0;
+/// Exact apex height, that every shot should ideally reach
+const TRUE_APEX: f32 = (LAUNCH_VELOCITY.y * LAUNCH_VELOCITY.y) / (2.0 * GRAVITY);
+/// Exact landing distance, that every shot should ideally reach
+const TRUE_DISTANCE: f32 = LAUNCH_VELOCITY.x * (2.0 * LAUNCH_VELOCITY.y / GRAVITY);
+
+const MAX_SHOTS: usize = 100;
+
+const PLOT_ORIGIN: Vec2 = Vec2::new(120.0, -100.0);
+const PLOT_SIZE: Vec2 = Vec2::new(300.0, 300.0);
+/// Landing distances shown in the scatter plot, as fractions of the true distance
+const DISTANCE_RANGE: (f32, f32) = (0.8, 1.2);
+/// Apex heights shown in the scatter plot, as fractions of the true apex
+const APEX_RANGE: (f32, f32) = (0.7, 1.05);
+
+const RENDER_LAYER: usize = 7;
+
+struct Systems;
+
+impl TimesteppedSystems for Systems {
+    fn get_systems_for_timestep<T: TimestepComponent>() -> ScheduleConfigs<ScheduleSystem> {
+        run::<T>.into_configs()
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_gizmo_group::<ProjectileGizmos>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, update_labels)
+        .add_systems(PostUpdate, draw)
+        .add_systems_with_timestep::<Systems>();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut despawns: ResMut<DespawnSystems>,
+    mut gizmos: ResMut<GizmoConfigStore>,
+) {
+    let despawn = commands.register_system(despawn);
+    despawns.0.push(despawn);
+    let spawn = commands.register_system(spawn);
+
+    let camera = commands.spawn_simulation_camera(RENDER_LAYER).id();
+    commands.insert_resource(ProjectilesMeta { camera, spawn });
+
+    gizmos.config_mut::<ProjectileGizmos>().0.render_layers = RenderLayers::layer(RENDER_LAYER);
+
+    commands.spawn((
+        Text2d::new(format!(
+            "Apex height against landing distance\nExact: {TRUE_APEX:.1}, {TRUE_DISTANCE:.1}"
+        )),
+        TextFont::from_font_size(16.0),
+        Anchor::BOTTOM_LEFT,
+        Transform::from_translation((PLOT_ORIGIN + Vec2::new(0.0, PLOT_SIZE.y + 4.0)).extend(0.0)),
+        RenderLayers::layer(RENDER_LAYER),
+    ));
+}
+
+fn despawn(
+    mut commands: Commands,
+    projectiles: Query<Entity, Or<(With<Projectile>, With<ShotsLabel>)>>,
+) {
+    for entity in projectiles.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn spawn(timestep: In<Timestep>, mut commands: Commands) {
+    let colour: Color = timestep.palette().sample_unchecked(0.0).into();
+
+    let projectile = commands
+        .spawn_with_timestep(
+            &timestep.0,
+            (
+                Projectile {
+                    timestep: timestep.0,
+                    velocity: LAUNCH_VELOCITY,
+                    apex: 0.0,
+                    shots: VecDeque::new(),
+                },
+                SimulationTransform::from_xyz(LAUNCHER.x, LAUNCHER.y, 1.0),
+                RenderLayers::layer(RENDER_LAYER),
+                Sprite::from_color(colour, Vec2::splat(10.0)),
+            ),
+        )
+        .id();
+
+    let y = PLOT_ORIGIN.y - 8.0 - ((timestep.index() as f32) * 60.0);
+    commands.spawn((
+        ShotsLabel(projectile),
+        Text2d::default(),
+        TextFont::from_font_size(16.0),
+        TextColor(colour),
+        Anchor::TOP_LEFT,
+        Transform::from_xyz(PLOT_ORIGIN.x, y, 0.0),
+        RenderLayers::layer(RENDER_LAYER),
+    ));
+}
+
+fn run<T: TimestepComponent>(
+    mut projectiles: Query<(&mut Projectile, &mut SimulationTransform), With<T>>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+
+    for (mut projectile, mut transform) in projectiles.iter_mut() {
+        // Semi-implicit Euler, as most character controllers use
+        projectile.velocity.y -= GRAVITY * dt;
+        let mut position = transform.translation.truncate() + (projectile.velocity * dt);
+        let height = position.y - LAUNCHER.y;
+
+        if height > 0.0 {
+            projectile.apex = projectile.apex.max(height);
+        } else {
+            // Record the shot where the simulation saw it land, as gameplay code would
+            let shot = Vec2::new(position.x - LAUNCHER.x, projectile.apex);
+            projectile.shots.push_back(shot);
+            if projectile.shots.len() > MAX_SHOTS {
+                projectile.shots.pop_front();
+            }
+
+            projectile.apex = 0.0;
+            projectile.velocity = LAUNCH_VELOCITY;
+            position = LAUNCHER;
+        }
+
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
+
+/// Position of a shot within the scatter plot
+fn plot_position(shot: Vec2) -> Vec2 {
+    let fraction = |value: f32, truth: f32, (min, max): (f32, f32)| {
+        (((value / truth) - min) / (max - min)).clamp(0.0, 1.0)
+    };
+
+    let x = fraction(shot.x, TRUE_DISTANCE, DISTANCE_RANGE);
+    let y = fraction(shot.y, TRUE_APEX, APEX_RANGE);
+    PLOT_ORIGIN + (Vec2::new(x, y) * PLOT_SIZE)
+}
+
+fn draw(projectiles: Query<&Projectile>, mut gizmos: Gizmos<ProjectileGizmos>) {
+    gizmos.rect_2d(
+        PLOT_ORIGIN + (PLOT_SIZE / 2.0),
+        PLOT_SIZE,
+        Color::WHITE.with_alpha(0.5),
+    );
+    gizmos.cross_2d(
+        plot_position(Vec2::new(TRUE_DISTANCE, TRUE_APEX)),
+        12.0,
+        Color::WHITE,
+    );
+
+    // Ground, and the path of an exact shot
+    gizmos.line_2d(
+        LAUNCHER - Vec2::X * 20.0,
+        LAUNCHER + Vec2::X * (TRUE_DISTANCE + 20.0),
+        Color::WHITE,
+    );
+    let flight_time = 2.0 * LAUNCH_VELOCITY.y / GRAVITY;
+    let path = (0..=32).map(|i| {
+        let t = flight_time * (i as f32) / 32.0;
+        LAUNCHER + (LAUNCH_VELOCITY * t) - Vec2::new(0.0, GRAVITY * t * t / 2.0)
+    });
+    gizmos.linestrip_2d(path, Color::WHITE.with_alpha(0.25));
+
+    for projectile in projectiles.iter() {
+        let colour = projectile.timestep.palette().sample_unchecked(0.0);
+        for &shot in &projectile.shots {
+            gizmos.circle_2d(plot_position(shot), 3.0, colour);
+        }
+    }
+}
+
+fn update_labels(mut labels: Query<(&mut Text2d, &ShotsLabel)>, projectiles: Query<&Projectile>) {
+    for (mut text, label) in labels.iter_mut() {
+        let Ok(projectile) = projectiles.get(label.0) else {
+            continue;
+        };
+
+        let shots = &projectile.shots;
+        let range = |value: fn(&Vec2) -> f32| {
+            let min = shots.iter().map(value).fold(f32::INFINITY, f32::min);
+            let max = shots.iter().map(value).fold(f32::NEG_INFINITY, f32::max);
+            (min, max)
+        };
+        text.0 = if shots.is_empty() {
+            projectile.timestep.name().to_owned()
+        } else {
+            let (min_apex, max_apex) = range(|shot| shot.y);
+            let (min_distance, max_distance) = range(|shot| shot.x);
+            format!(
+                "{}: {} shots\nApex {min_apex:.1} - {max_apex:.1}\nDistance {min_distance:.1} - {max_distance:.1}",
+                projectile.timestep.name(),
+                shots.len(),
+            )
+        };
+    }
+}
@@ -0,0 +1,213 @@
+use std::time::Duration;
+
+use bevy::{
+    camera::visibility::RenderLayers,
+    ecs::{
+        schedule::ScheduleConfigs,
+        system::{ScheduleSystem, SystemId},
+    },
+    prelude::*,
+    sprite::Anchor,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    configuration::{
+        ActiveTimesteps, AppExt, CommandsExt, DespawnSystems, SimulationMeta, Timestep,
+        TimestepComponent, TimesteppedSystems,
+    },
+    interpolation::SimulationTransform,
+};
+
+#[derive(Resource)]
+pub struct RapidFireMeta {
+    pub camera: Entity,
+    pub spawn: SystemId<In<Timestep>>,
+}
+
+impl SimulationMeta for RapidFireMeta {
+    fn get(&self) -> (Entity, SystemId<In<Timestep>>) {
+        (self.camera, self.spawn)
+    }
+}
+
+#[derive(Resource, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RapidFireConfig {
+    /// Shots per second
+    pub rate: f32,
+    /// Fire once for every time the timer finished during the update, rather than at most once per update
+    pub accurate: bool,
+}
+
+impl Default for RapidFireConfig {
+    fn default() -> Self {
+        // The classic mistake, which loses shots whenever the rate is above the update rate
+        Self {
+            rate: 50.0,
+            accurate: false,
+        }
+    }
+}
+
+pub const MIN_FIRE_RATE: f32 = 1.0;
+pub const MAX_FIRE_RATE: f32 = 200.0;
+
+#[derive(Component)]
+struct Gun {
+    timestep: Timestep,
+    timer: Timer,
+    fired: u32,
+    expected: f32,
+}
+
+#[derive(Component)]
+struct Bullet;
+
+const BULLET_SPEED: f32 = 800.0;
+const MUZZLE_X: f32 = -300.0;
+/// Bullets are despawned once they travel past this
+const RANGE_X: f32 = 400.0;
+const LANE_HEIGHT: f32 = 150.0;
+
+const RENDER_LAYER: usize = 13;
+
+struct Systems;
+
+impl TimesteppedSystems for Systems {
+    fn get_systems_for_timestep<T: TimestepComponent>() -> ScheduleConfigs<ScheduleSystem> {
+        // Bullets are fired already moved by however much of the update they were due for
+        (move_bullets::<T>, fire::<T>).chain()
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<RapidFireConfig>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, update_labels)
+        .add_systems_with_timestep::<Systems>();
+}
+
+fn setup(mut commands: Commands, mut despawns: ResMut<DespawnSystems>) {
+    let despawn = commands.register_system(despawn);
+    despawns.0.push(despawn);
+    let spawn = commands.register_system(spawn);
+
+    let camera = commands.spawn_simulation_camera(RENDER_LAYER).id();
+    commands.insert_resource(RapidFireMeta { camera, spawn });
+}
+
+fn despawn(mut commands: Commands, entities: Query<Entity, Or<(With<Gun>, With<Bullet>)>>) {
+    for entity in entities.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn spawn(
+    timestep: In<Timestep>,
+    mut commands: Commands,
+    active_timesteps: Res<ActiveTimesteps>,
+    config: Res<RapidFireConfig>,
+) {
+    let colour: Color = timestep.palette().sample_unchecked(0.0).into();
+
+    let y = active_timesteps.lane_centre(timestep.0, LANE_HEIGHT);
+
+    commands.spawn_with_timestep(
+        &timestep.0,
+        (
+            Gun {
+                timestep: timestep.0,
+                timer: Timer::from_seconds(config.rate.recip(), TimerMode::Repeating),
+                fired: 0,
+                expected: 0.0,
+            },
+            Text2d::default(),
+            TextFont::from_font_size(16.0),
+            TextColor(colour),
+            Anchor::TOP_LEFT,
+            Transform::from_xyz(MUZZLE_X - 24.0, y - 16.0, 0.0),
+            RenderLayers::layer(RENDER_LAYER),
+            children![(
+                Transform::from_xyz(12.0, 16.0, 0.0),
+                RenderLayers::layer(RENDER_LAYER),
+                Sprite::from_color(colour, Vec2::new(24.0, 12.0)),
+            )],
+        ),
+    );
+}
+
+fn move_bullets<T: TimestepComponent>(
+    mut commands: Commands,
+    mut bullets: Query<(Entity, &mut SimulationTransform), (With<Bullet>, With<T>)>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut transform) in bullets.iter_mut() {
+        transform.translation.x += BULLET_SPEED * dt;
+        if transform.translation.x > RANGE_X {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn fire<T: TimestepComponent>(
+    mut commands: Commands,
+    mut guns: Query<(&mut Gun, &Transform), With<T>>,
+    config: Res<RapidFireConfig>,
+    time: Res<Time>,
+) {
+    for (mut gun, transform) in guns.iter_mut() {
+        let colour: Color = gun.timestep.palette().sample_unchecked(0.0).into();
+        let period = Duration::from_secs_f32(config.rate.recip());
+        if gun.timer.duration() != period {
+            gun.timer.set_duration(period);
+        }
+        gun.timer.tick(time.delta());
+        gun.expected += config.rate * time.delta_secs();
+
+        let shots = if config.accurate {
+            gun.timer.times_finished_this_tick()
+        } else {
+            gun.timer.just_finished() as u32
+        };
+        gun.fired += shots;
+
+        let y = transform.translation.y + 16.0;
+        for i in 0..shots {
+            // Time since the shot was due, which the naive handling ignores
+            let age = if config.accurate {
+                gun.timer.elapsed_secs() + ((shots - i - 1) as f32) * period.as_secs_f32()
+            } else {
+                0.0
+            };
+
+            commands.spawn_with_timestep(
+                &gun.timestep,
+                (
+                    Bullet,
+                    SimulationTransform::from_xyz(MUZZLE_X + (BULLET_SPEED * age), y, 1.0),
+                    RenderLayers::layer(RENDER_LAYER),
+                    Sprite::from_color(colour, Vec2::new(8.0, 4.0)),
+                ),
+            );
+        }
+    }
+}
+
+fn update_labels(mut guns: Query<(&mut Text2d, &Gun)>) {
+    for (mut text, gun) in guns.iter_mut() {
+        let percentage = if gun.expected > 0.0 {
+            (gun.fired as f32) / gun.expected * 100.0
+        } else {
+            100.0
+        };
+        text.0 = format!(
+            "{}\nFired {} of {:.0} ({percentage:.0}%)",
+            gun.timestep.name(),
+            gun.fired,
+            gun.expected,
+        );
+    }
+}
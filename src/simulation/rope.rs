@@ -0,0 +1,239 @@
+use bevy::{
+    camera::visibility::RenderLayers,
+    ecs::{
+        schedule::ScheduleConfigs,
+        system::{ScheduleSystem, SystemId},
+    },
+    prelude::*,
+    sprite::Anchor,
+    window::PrimaryWindow,
+};
+
+use crate::{
+    configuration::{
+        AppExt, CommandsExt, DespawnSystems, SimulationMeta, Timestep, TimestepComponent,
+        TimesteppedSystems,
+    },
+    interpolation::SimulationTransform,
+};
+
+#[derive(Resource)]
+pub struct RopeMeta {
+    pub camera: Entity,
+    pub spawn: SystemId<In<Timestep>>,
+}
+
+impl SimulationMeta for RopeMeta {
+    fn get(&self) -> (Entity, SystemId<In<Timestep>>) {
+        (self.camera, self.spawn)
+    }
+}
+
+#[derive(Component)]
+struct RopeCamera;
+
+#[derive(Component)]
+struct Rope {
+    timestep: Timestep,
+    /// Horizontal distance from the cursor, so that the ropes don't overlap
+    offset: f32,
+    anchor: Vec2,
+    points: Vec<Entity>,
+    /// How much longer than its rest length the rope is, as a fraction of the rest length
+    stretch: f32,
+    /// Largest stretch since spawning
+    max_stretch: f32,
+}
+
+#[derive(Component)]
+struct Point {
+    /// Position at the end of the previous update, which implicitly stores the velocity
+    previous: Vec2,
+}
+
+#[derive(GizmoConfigGroup, Default, Reflect)]
+struct RopeGizmos;
+
+const POINT_COUNT: usize = 20;
+const SEGMENT_LENGTH: f32 = 15.0;
+const REST_LENGTH: f32 = SEGMENT_LENGTH * (POINT_COUNT as f32);
+const GRAVITY: f32 = 1500.0;
+/// Fraction of the velocity kept each update, regardless of delta time, as is common with Verlet integration
+const DAMPING: f32 = 0.99;
+/// Passes over every constraint each update. Fewer passes leave the rope stretchier.
+const ITERATIONS: usize = 10;
+const SPACING: f32 = 60.0;
+
+const RENDER_LAYER: usize = 12;
+
+struct Systems;
+
+impl TimesteppedSystems for Systems {
+    fn get_systems_for_timestep<T: TimestepComponent>() -> ScheduleConfigs<ScheduleSystem> {
+        run::<T>.into_configs()
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_gizmo_group::<RopeGizmos>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, update_labels)
+        .add_systems(PostUpdate, draw.after(TransformSystems::Propagate))
+        .add_systems_with_timestep::<Systems>();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut despawns: ResMut<DespawnSystems>,
+    mut gizmos: ResMut<GizmoConfigStore>,
+) {
+    let despawn = commands.register_system(despawn);
+    despawns.0.push(despawn);
+    let spawn = commands.register_system(spawn);
+
+    let camera = commands
+        .spawn_simulation_camera(RENDER_LAYER)
+        .insert(RopeCamera)
+        .id();
+    commands.insert_resource(RopeMeta { camera, spawn });
+
+    gizmos.config_mut::<RopeGizmos>().0.render_layers = RenderLayers::layer(RENDER_LAYER);
+}
+
+fn despawn(mut commands: Commands, ropes: Query<(Entity, &Rope)>) {
+    for (entity, rope) in ropes.iter() {
+        for &point in &rope.points {
+            commands.entity(point).despawn();
+        }
+        commands.entity(entity).despawn();
+    }
+}
+
+fn spawn(timestep: In<Timestep>, mut commands: Commands) {
+    let colour: Color = timestep.palette().sample_unchecked(0.0).into();
+    let offset = ((timestep.index() as f32) - 1.5) * SPACING;
+    let anchor = Vec2::new(offset, 200.0);
+
+    // Starts out hanging straight down, until the cursor moves
+    let points = (1..=POINT_COUNT)
+        .map(|i| {
+            let position = anchor - Vec2::new(0.0, (i as f32) * SEGMENT_LENGTH);
+            commands
+                .spawn_with_timestep(
+                    &timestep.0,
+                    (
+                        Point { previous: position },
+                        SimulationTransform::from_xyz(position.x, position.y, 1.0),
+                        RenderLayers::layer(RENDER_LAYER),
+                        Sprite::from_color(colour, Vec2::splat(6.0)),
+                    ),
+                )
+                .id()
+        })
+        .collect();
+
+    commands.spawn_with_timestep(
+        &timestep.0,
+        (
+            Rope {
+                timestep: timestep.0,
+                offset,
+                anchor,
+                points,
+                stretch: 0.0,
+                max_stretch: 0.0,
+            },
+            Text2d::default(),
+            TextFont::from_font_size(16.0),
+            TextColor(colour),
+            Anchor::TOP_LEFT,
+            Transform::from_xyz(((timestep.index() as f32) * 170.0) - 300.0, 330.0, 0.0),
+            RenderLayers::layer(RENDER_LAYER),
+        ),
+    );
+}
+
+fn run<T: TimestepComponent>(
+    mut ropes: Query<&mut Rope, With<T>>,
+    mut points: Query<(&mut Point, &mut SimulationTransform), With<T>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<RopeCamera>>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+    let cursor = window
+        .cursor_position()
+        .and_then(|position| camera.0.viewport_to_world_2d(camera.1, position).ok());
+
+    for mut rope in ropes.iter_mut() {
+        if let Some(cursor) = cursor {
+            rope.anchor = cursor + Vec2::new(rope.offset, 0.0);
+        }
+
+        let mut positions = Vec::with_capacity(POINT_COUNT + 1);
+        positions.push(rope.anchor);
+        for &entity in &rope.points {
+            let (mut point, transform) = points.get_mut(entity).unwrap();
+            let position = transform.translation.truncate();
+
+            // Position Verlet, which assumes that the delta time is the same as the previous update's
+            let velocity = (position - point.previous) * DAMPING;
+            point.previous = position;
+            positions.push(position + velocity - Vec2::new(0.0, GRAVITY * dt * dt));
+        }
+
+        // Gauss-Seidel relaxation of every segment's length, with the first point pinned to the anchor
+        for _ in 0..ITERATIONS {
+            for i in 1..=POINT_COUNT {
+                let offset = positions[i] - positions[i - 1];
+                let error = offset.length() - SEGMENT_LENGTH;
+                let correction = offset.normalize_or_zero() * error;
+                if i == 1 {
+                    positions[i] -= correction;
+                } else {
+                    positions[i - 1] += correction / 2.0;
+                    positions[i] -= correction / 2.0;
+                }
+            }
+        }
+
+        let length: f32 = positions
+            .windows(2)
+            .map(|pair| pair[0].distance(pair[1]))
+            .sum();
+        rope.stretch = (length / REST_LENGTH) - 1.0;
+        rope.max_stretch = rope.max_stretch.max(rope.stretch);
+
+        for (i, &entity) in rope.points.iter().enumerate() {
+            let (_, mut transform) = points.get_mut(entity).unwrap();
+            transform.translation = positions[i + 1].extend(transform.translation.z);
+        }
+    }
+}
+
+fn draw(
+    ropes: Query<&Rope>,
+    points: Query<&Transform, With<Point>>,
+    mut gizmos: Gizmos<RopeGizmos>,
+) {
+    for rope in ropes.iter() {
+        let colour = rope.timestep.palette().sample_unchecked(0.0);
+        let positions = rope
+            .points
+            .iter()
+            .filter_map(|&entity| points.get(entity).ok())
+            .map(|transform| transform.translation.truncate());
+        gizmos.linestrip_2d(std::iter::once(rope.anchor).chain(positions), colour);
+    }
+}
+
+fn update_labels(mut ropes: Query<(&mut Text2d, &Rope), Changed<Rope>>) {
+    for (mut text, rope) in ropes.iter_mut() {
+        text.0 = format!(
+            "{}\nStretch: {:.1}%\nMost: {:.1}%",
+            rope.timestep.name(),
+            rope.stretch * 100.0,
+            rope.max_stretch * 100.0,
+        );
+    }
+}
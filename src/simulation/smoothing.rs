@@ -0,0 +1,227 @@
+use bevy::{
+    camera::visibility::RenderLayers,
+    ecs::{
+        schedule::ScheduleConfigs,
+        system::{ScheduleSystem, SystemId},
+    },
+    prelude::*,
+    sprite::Anchor,
+};
+
+use crate::{
+    configuration::{
+        ActiveTimesteps, AppExt, CommandsExt, DespawnSystems, SimulationMeta, Timestep,
+        TimestepComponent, TimesteppedSystems,
+    },
+    interpolation::SimulationTransform,
+};
+
+#[derive(Resource)]
+pub struct SmoothingMeta {
+    pub camera: Entity,
+    pub spawn: SystemId<In<Timestep>>,
+}
+
+impl SimulationMeta for SmoothingMeta {
+    fn get(&self) -> (Entity, SystemId<In<Timestep>>) {
+        (self.camera, self.spawn)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Kind {
+    /// `x = lerp(x, target, k)`, with the same `k` every update
+    NaiveLerp,
+    /// `x = lerp(x, target, 1 - exp(-rate * dt))`
+    DecayLerp,
+    /// `velocity *= d`, with the same `d` every update
+    NaiveDamping,
+    /// `velocity *= d.powf(dt * REFERENCE_RATE)`
+    DecayDamping,
+}
+
+impl Kind {
+    const ALL: [Self; 4] = [
+        Self::NaiveLerp,
+        Self::DecayLerp,
+        Self::NaiveDamping,
+        Self::DecayDamping,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::NaiveLerp => "lerp(x, target, k)",
+            Self::DecayLerp => "lerp(x, target, 1 - exp(-rate * dt))",
+            Self::NaiveDamping => "velocity *= d",
+            Self::DecayDamping => "velocity *= d.powf(dt * 60)",
+        }
+    }
+}
+
+#[derive(Component)]
+struct Smoother {
+    timestep: Timestep,
+    kind: Kind,
+    velocity: f32,
+    /// Time since spawning, so that every timestep switches targets on its own clock
+    elapsed: f32,
+    /// Whether the target is currently on the right
+    right: bool,
+}
+
+/// Everything spawned alongside the smoothers, that isn't simulated
+#[derive(Component)]
+struct Scenery;
+
+#[derive(GizmoConfigGroup, Default, Reflect)]
+struct SmoothingGizmos;
+
+/// Update rate that the naive constants were tuned for, and that the correct forms are equivalent to
+const REFERENCE_RATE: f32 = 60.0;
+/// Fraction of the remaining distance covered each update, at the reference rate
+const LERP_FACTOR: f32 = 0.1;
+/// Fraction of the velocity kept each update, at the reference rate
+const DAMPING_FACTOR: f32 = 0.99;
+/// Speed each damped smoother is launched towards its new target with.
+/// At the reference rate, this would eventually coast to a stop at roughly the target.
+const IMPULSE: f32 = (RIGHT - LEFT) * REFERENCE_RATE * (1.0 - DAMPING_FACTOR);
+/// Seconds between each switch of the target
+const PERIOD: f32 = 3.0;
+
+const LEFT: f32 = -200.0;
+const RIGHT: f32 = 200.0;
+const SIZE: f32 = 16.0;
+const ROW_HEIGHT: f32 = 28.0;
+const LANE_HEIGHT: f32 = ROW_HEIGHT * 5.0;
+
+const RENDER_LAYER: usize = 9;
+
+struct Systems;
+
+impl TimesteppedSystems for Systems {
+    fn get_systems_for_timestep<T: TimestepComponent>() -> ScheduleConfigs<ScheduleSystem> {
+        run::<T>.into_configs()
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_gizmo_group::<SmoothingGizmos>()
+        .add_systems(Startup, setup)
+        .add_systems(PostUpdate, draw)
+        .add_systems_with_timestep::<Systems>();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut despawns: ResMut<DespawnSystems>,
+    mut gizmos: ResMut<GizmoConfigStore>,
+) {
+    let despawn = commands.register_system(despawn);
+    despawns.0.push(despawn);
+    let spawn = commands.register_system(spawn);
+
+    let camera = commands.spawn_simulation_camera(RENDER_LAYER).id();
+    commands.insert_resource(SmoothingMeta { camera, spawn });
+
+    gizmos.config_mut::<SmoothingGizmos>().0.render_layers = RenderLayers::layer(RENDER_LAYER);
+}
+
+fn despawn(mut commands: Commands, entities: Query<Entity, Or<(With<Smoother>, With<Scenery>)>>) {
+    for entity in entities.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn spawn(timestep: In<Timestep>, mut commands: Commands, active_timesteps: Res<ActiveTimesteps>) {
+    let colour: Color = timestep.palette().sample_unchecked(0.0).into();
+
+    let top = active_timesteps.lane_centre(timestep.0, LANE_HEIGHT) + (LANE_HEIGHT / 2.0);
+
+    for (row, kind) in Kind::ALL.into_iter().enumerate() {
+        let y = top - ((row as f32) + 1.0) * ROW_HEIGHT;
+
+        commands.spawn_with_timestep(
+            &timestep.0,
+            (
+                Smoother {
+                    timestep: timestep.0,
+                    kind,
+                    velocity: 0.0,
+                    elapsed: 0.0,
+                    right: false,
+                },
+                SimulationTransform::from_xyz(LEFT, y, 1.0),
+                RenderLayers::layer(RENDER_LAYER),
+                Sprite::from_color(colour, Vec2::splat(SIZE)),
+            ),
+        );
+        commands.spawn((
+            Scenery,
+            Text2d::new(format!("{}: {}", timestep.name(), kind.name())),
+            TextFont::from_font_size(14.0),
+            TextColor(colour),
+            Anchor::CENTER_LEFT,
+            Transform::from_xyz(RIGHT + SIZE * 2.0, y, 0.0),
+            RenderLayers::layer(RENDER_LAYER),
+        ));
+    }
+}
+
+fn run<T: TimestepComponent>(
+    mut smoothers: Query<(&mut Smoother, &mut SimulationTransform), With<T>>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+
+    for (mut smoother, mut transform) in smoothers.iter_mut() {
+        smoother.elapsed += dt;
+        let right = (smoother.elapsed / PERIOD) as u32 % 2 == 1;
+        let switched = right != smoother.right;
+        smoother.right = right;
+
+        let target = if right { RIGHT } else { LEFT };
+        let x = &mut transform.translation.x;
+
+        match smoother.kind {
+            Kind::NaiveLerp => {
+                *x = x.lerp(target, LERP_FACTOR);
+            }
+            Kind::DecayLerp => {
+                // The rate that loses the same fraction of the distance per second as the naive form does at the reference rate
+                let rate = -(1.0 - LERP_FACTOR).ln() * REFERENCE_RATE;
+                *x = x.lerp(target, 1.0 - (-rate * dt).exp());
+            }
+            Kind::NaiveDamping | Kind::DecayDamping => {
+                if switched {
+                    smoother.velocity = if right { IMPULSE } else { -IMPULSE };
+                }
+                smoother.velocity *= if smoother.kind == Kind::NaiveDamping {
+                    DAMPING_FACTOR
+                } else {
+                    DAMPING_FACTOR.powf(dt * REFERENCE_RATE)
+                };
+                *x += smoother.velocity * dt;
+            }
+        }
+    }
+}
+
+fn draw(smoothers: Query<(&Smoother, &SimulationTransform)>, mut gizmos: Gizmos<SmoothingGizmos>) {
+    for (smoother, transform) in smoothers.iter() {
+        let y = transform.translation.y;
+        let colour = smoother.timestep.palette().sample_unchecked(0.0);
+
+        gizmos.line_2d(
+            Vec2::new(LEFT, y),
+            Vec2::new(RIGHT, y),
+            Color::WHITE.with_alpha(0.25),
+        );
+
+        let target = if smoother.right { RIGHT } else { LEFT };
+        gizmos.line_2d(
+            Vec2::new(target, y - SIZE),
+            Vec2::new(target, y + SIZE),
+            colour,
+        );
+    }
+}
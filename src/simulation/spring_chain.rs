@@ -0,0 +1,290 @@
+use std::f32::consts::PI;
+
+use bevy::{
+    camera::visibility::RenderLayers,
+    ecs::{
+        schedule::ScheduleConfigs,
+        system::{ScheduleSystem, SystemId},
+    },
+    prelude::*,
+    sprite::Anchor,
+};
+
+use crate::{
+    configuration::{
+        AppExt, CommandsExt, DespawnSystems, SimulationMeta, Timestep, TimestepComponent,
+        TimesteppedSystems,
+    },
+    interpolation::SimulationTransform,
+};
+
+#[derive(Resource)]
+pub struct SpringChainMeta {
+    pub camera: Entity,
+    pub spawn: SystemId<In<Timestep>>,
+}
+
+impl SimulationMeta for SpringChainMeta {
+    fn get(&self) -> (Entity, SystemId<In<Timestep>>) {
+        (self.camera, self.spawn)
+    }
+}
+
+/// Spring constant of every spring in the chain, per unit of mass
+#[derive(Resource, Clone, Copy, PartialEq, Debug)]
+pub struct SpringStiffness(pub f32);
+
+impl Default for SpringStiffness {
+    fn default() -> Self {
+        Self(500.0)
+    }
+}
+
+impl SpringStiffness {
+    /// Largest delta time that explicit Euler integration stays stable with, at least while the
+    /// springs are stretched along their length.
+    ///
+    /// Each mode of the chain is a damped oscillator, scaled by an eigenvalue of the chain. While
+    /// every mode is underdamped they share the same limit of damping over stiffness, but once
+    /// the stiffest mode is overdamped its faster real decay rate sets a tighter limit.
+    pub fn critical_delta_secs(self) -> f32 {
+        // Largest eigenvalue of a chain of springs that is fixed at one end and free at the other
+        let eigenvalue = 4.0
+            * ((((2 * MASS_COUNT) - 1) as f32 * PI) / ((4 * MASS_COUNT) + 2) as f32)
+                .sin()
+                .powi(2);
+        let damping = DAMPING * eigenvalue;
+        let stiffness = self.0 * eigenvalue;
+        let discriminant = damping.powi(2) - (4.0 * stiffness);
+        if discriminant > 0.0 {
+            4.0 / (damping + discriminant.sqrt())
+        } else {
+            DAMPING / self.0
+        }
+    }
+}
+
+pub const MIN_STIFFNESS: f32 = 50.0;
+pub const MAX_STIFFNESS: f32 = 2000.0;
+
+#[derive(Component)]
+struct Chain {
+    timestep: Timestep,
+    anchor: Vec2,
+    masses: Vec<Entity>,
+    explosions: u32,
+}
+
+#[derive(Component, Default)]
+struct Mass {
+    velocity: Vec2,
+}
+
+#[derive(Component)]
+struct CriticalDeltaText;
+
+#[derive(GizmoConfigGroup, Default, Reflect)]
+struct SpringGizmos;
+
+const MASS_COUNT: usize = 10;
+const REST_LENGTH: f32 = 24.0;
+/// Relative damping between neighbouring masses
+const DAMPING: f32 = 20.0;
+const GRAVITY: f32 = 600.0;
+/// A chain is considered to have exploded once any mass gets this far from its anchor
+const EXPLODED_DISTANCE: f32 = 5000.0;
+
+const RENDER_LAYER: usize = 5;
+
+struct Systems;
+
+impl TimesteppedSystems for Systems {
+    fn get_systems_for_timestep<T: TimestepComponent>() -> ScheduleConfigs<ScheduleSystem> {
+        run::<T>.into_configs()
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<SpringStiffness>()
+        .init_gizmo_group::<SpringGizmos>()
+        .add_systems(Startup, setup)
+        .add_systems(
+            Update,
+            (
+                update_labels,
+                update_critical_delta.run_if(resource_changed::<SpringStiffness>),
+            ),
+        )
+        .add_systems(PostUpdate, draw.after(TransformSystems::Propagate))
+        .add_systems_with_timestep::<Systems>();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut despawns: ResMut<DespawnSystems>,
+    mut gizmos: ResMut<GizmoConfigStore>,
+) {
+    let despawn = commands.register_system(despawn);
+    despawns.0.push(despawn);
+    let spawn = commands.register_system(spawn);
+
+    let camera = commands.spawn_simulation_camera(RENDER_LAYER).id();
+    commands.insert_resource(SpringChainMeta { camera, spawn });
+
+    gizmos.config_mut::<SpringGizmos>().0.render_layers = RenderLayers::layer(RENDER_LAYER);
+
+    commands.spawn((
+        CriticalDeltaText,
+        Text2d::default(),
+        TextFont::from_font_size(18.0),
+        Anchor::TOP_LEFT,
+        Transform::from_xyz(-300.0, 330.0, 0.0),
+        RenderLayers::layer(RENDER_LAYER),
+    ));
+}
+
+fn despawn(mut commands: Commands, chains: Query<(Entity, &Chain)>) {
+    for (entity, chain) in chains.iter() {
+        for &mass in &chain.masses {
+            commands.entity(mass).despawn();
+        }
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Starts out horizontal, so that it swings down and oscillates
+fn rest_positions(anchor: Vec2) -> impl Iterator<Item = Vec2> {
+    (1..=MASS_COUNT).map(move |i| anchor + Vec2::new((i as f32) * REST_LENGTH, 0.0))
+}
+
+fn spawn(timestep: In<Timestep>, mut commands: Commands) {
+    let colour: Color = timestep.palette().sample_unchecked(0.0).into();
+    let anchor = Vec2::new(((timestep.index() as f32) * 150.0) - 300.0, 250.0);
+
+    let masses = rest_positions(anchor)
+        .map(|position| {
+            commands
+                .spawn_with_timestep(
+                    &timestep.0,
+                    (
+                        Mass::default(),
+                        SimulationTransform::from_xyz(position.x, position.y, 1.0),
+                        RenderLayers::layer(RENDER_LAYER),
+                        Sprite::from_color(colour, Vec2::splat(8.0)),
+                    ),
+                )
+                .id()
+        })
+        .collect();
+
+    commands.spawn_with_timestep(
+        &timestep.0,
+        (
+            Chain {
+                timestep: timestep.0,
+                anchor,
+                masses,
+                explosions: 0,
+            },
+            Text2d::default(),
+            TextFont::from_font_size(16.0),
+            TextColor(colour),
+            Anchor::BOTTOM_CENTER,
+            Transform::from_xyz(anchor.x, anchor.y + 8.0, 0.0),
+            RenderLayers::layer(RENDER_LAYER),
+            Sprite::from_color(colour, Vec2::splat(4.0)),
+        ),
+    );
+}
+
+fn run<T: TimestepComponent>(
+    mut chains: Query<&mut Chain, With<T>>,
+    mut masses: Query<(&mut Mass, &mut SimulationTransform), With<T>>,
+    stiffness: Res<SpringStiffness>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+
+    for mut chain in chains.iter_mut() {
+        let mut positions = Vec::with_capacity(MASS_COUNT + 1);
+        let mut velocities = Vec::with_capacity(MASS_COUNT + 1);
+        positions.push(chain.anchor);
+        velocities.push(Vec2::ZERO);
+        for &entity in &chain.masses {
+            let (mass, transform) = masses.get(entity).unwrap();
+            positions.push(transform.translation.truncate());
+            velocities.push(mass.velocity);
+        }
+
+        // Forces on each mass, from the springs and dampers either side of it
+        let mut forces = [Vec2::new(0.0, -GRAVITY); MASS_COUNT + 1];
+        for i in 1..=MASS_COUNT {
+            let offset = positions[i] - positions[i - 1];
+            let length = offset.length();
+            let direction = offset.normalize_or_zero();
+            let relative_velocity = (velocities[i] - velocities[i - 1]).dot(direction);
+            let tension = (stiffness.0 * (length - REST_LENGTH)) + (DAMPING * relative_velocity);
+            forces[i] -= direction * tension;
+            forces[i - 1] += direction * tension;
+        }
+
+        // Explicit Euler: positions are advanced with the velocities from the start of the update.
+        // Unstable whenever the delta time is above `SpringStiffness::critical_delta_secs`.
+        for i in 1..=MASS_COUNT {
+            positions[i] += velocities[i] * dt;
+            velocities[i] += forces[i] * dt;
+        }
+
+        let exploded = positions[1..].iter().any(|position| {
+            !position.is_finite() || position.distance(chain.anchor) > EXPLODED_DISTANCE
+        });
+        if exploded {
+            chain.explosions += 1;
+            for (i, position) in rest_positions(chain.anchor).enumerate() {
+                positions[i + 1] = position;
+                velocities[i + 1] = Vec2::ZERO;
+            }
+        }
+
+        for (i, &entity) in chain.masses.iter().enumerate() {
+            let (mut mass, mut transform) = masses.get_mut(entity).unwrap();
+            mass.velocity = velocities[i + 1];
+            transform.translation = positions[i + 1].extend(transform.translation.z);
+        }
+    }
+}
+
+fn draw(
+    chains: Query<&Chain>,
+    masses: Query<&Transform, With<Mass>>,
+    mut gizmos: Gizmos<SpringGizmos>,
+) {
+    for chain in chains.iter() {
+        let colour = chain.timestep.palette().sample_unchecked(0.0);
+        let points = chain
+            .masses
+            .iter()
+            .filter_map(|&entity| masses.get(entity).ok())
+            .map(|transform| transform.translation.truncate());
+        gizmos.linestrip_2d(std::iter::once(chain.anchor).chain(points), colour);
+    }
+}
+
+fn update_labels(mut chains: Query<(&mut Text2d, &Chain), Changed<Chain>>) {
+    for (mut text, chain) in chains.iter_mut() {
+        text.0 = format!("{}\n{} explosions", chain.timestep.name(), chain.explosions);
+    }
+}
+
+fn update_critical_delta(
+    mut text: Single<&mut Text2d, With<CriticalDeltaText>>,
+    stiffness: Res<SpringStiffness>,
+) {
+    let critical = stiffness.critical_delta_secs();
+    text.0 = format!(
+        "Stiffness: {:.0}\nExplicit Euler explodes above a delta time of {:.1} ms ({:.1} Hz)",
+        stiffness.0,
+        critical * 1000.0,
+        critical.recip()
+    );
+}
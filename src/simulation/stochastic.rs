@@ -0,0 +1,249 @@
+use bevy::{
+    camera::visibility::RenderLayers,
+    ecs::{
+        schedule::ScheduleConfigs,
+        system::{ScheduleSystem, SystemId},
+    },
+    prelude::*,
+    sprite::Anchor,
+};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use rand_distr::{Distribution, Poisson};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    configuration::{
+        ActiveTimesteps, AppExt, CommandsExt, DespawnSystems, SimulationMeta, Timestep,
+        TimestepComponent, TimesteppedSystems,
+    },
+    interpolation::SimulationTransform,
+};
+
+#[derive(Resource)]
+pub struct StochasticMeta {
+    pub camera: Entity,
+    pub spawn: SystemId<In<Timestep>>,
+}
+
+impl SimulationMeta for StochasticMeta {
+    fn get(&self) -> (Entity, SystemId<In<Timestep>>) {
+        (self.camera, self.spawn)
+    }
+}
+
+#[derive(Resource, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StochasticConfig {
+    /// Average events per second that every spawner aims for
+    pub rate: f32,
+    /// Every spawner's random number generator is seeded from this. Only the No Delta and Fixed timesteps
+    /// repeat the same events for a seed, as the others draw against frame timings that differ between runs
+    pub seed: u64,
+}
+
+impl Default for StochasticConfig {
+    fn default() -> Self {
+        Self {
+            rate: 20.0,
+            seed: 0,
+        }
+    }
+}
+
+pub const MIN_EVENT_RATE: f32 = 1.0;
+pub const MAX_EVENT_RATE: f32 = 60.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Method {
+    /// `random() < rate / REFERENCE_RATE` each update, which assumes a fixed update rate
+    PerUpdate,
+    /// `random() < rate * dt` each update, which can't spawn more than once per update
+    ScaledByDelta,
+    /// A Poisson draw with a mean of `rate * dt` each update, which is exact for any delta time
+    Poisson,
+}
+
+impl Method {
+    const ALL: [Self; 3] = [Self::PerUpdate, Self::ScaledByDelta, Self::Poisson];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::PerUpdate => "random() < rate / 60",
+            Self::ScaledByDelta => "random() < rate * dt",
+            Self::Poisson => "Poisson(rate * dt)",
+        }
+    }
+}
+
+#[derive(Component)]
+struct Spawner {
+    timestep: Timestep,
+    method: Method,
+    rng: StdRng,
+    spawned: u32,
+    expected: f32,
+}
+
+/// Drifts away from its spawner and fades out, so that the spawn rate is visible
+#[derive(Component)]
+struct Blip {
+    age: f32,
+}
+
+/// Update rate that the per-update probability was tuned for
+const REFERENCE_RATE: f32 = 60.0;
+
+const BLIP_SPEED: f32 = 200.0;
+/// Seconds before each blip is despawned
+const LIFETIME: f32 = 1.5;
+const ROW_HEIGHT: f32 = 40.0;
+const LANE_HEIGHT: f32 = ROW_HEIGHT * 4.0;
+const SPAWNER_X: f32 = -300.0;
+/// Just past where the blips fade out
+const LABEL_X: f32 = SPAWNER_X + (BLIP_SPEED * LIFETIME) + 16.0;
+
+const RENDER_LAYER: usize = 14;
+
+struct Systems;
+
+impl TimesteppedSystems for Systems {
+    fn get_systems_for_timestep<T: TimestepComponent>() -> ScheduleConfigs<ScheduleSystem> {
+        (move_blips::<T>, spawn_blips::<T>).chain()
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<StochasticConfig>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, update_labels)
+        .add_systems_with_timestep::<Systems>();
+}
+
+fn setup(mut commands: Commands, mut despawns: ResMut<DespawnSystems>) {
+    let despawn = commands.register_system(despawn);
+    despawns.0.push(despawn);
+    let spawn = commands.register_system(spawn);
+
+    let camera = commands.spawn_simulation_camera(RENDER_LAYER).id();
+    commands.insert_resource(StochasticMeta { camera, spawn });
+}
+
+fn despawn(mut commands: Commands, entities: Query<Entity, Or<(With<Spawner>, With<Blip>)>>) {
+    for entity in entities.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn spawn(
+    timestep: In<Timestep>,
+    mut commands: Commands,
+    active_timesteps: Res<ActiveTimesteps>,
+    config: Res<StochasticConfig>,
+) {
+    let colour: Color = timestep.palette().sample_unchecked(0.0).into();
+
+    let top = active_timesteps.lane_centre(timestep.0, LANE_HEIGHT) + (LANE_HEIGHT / 2.0);
+
+    for (row, method) in Method::ALL.into_iter().enumerate() {
+        let y = top - ((row as f32) + 1.0) * ROW_HEIGHT;
+        // Every spawner gets its own sequence, that doesn't depend on the other active timesteps.
+        // The seed is scrambled first, so that nearby seeds don't just swap sequences between spawners.
+        let seed = config.seed.wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            ^ ((timestep.index() * Method::ALL.len() + row) as u64);
+
+        commands.spawn_with_timestep(
+            &timestep.0,
+            (
+                Spawner {
+                    timestep: timestep.0,
+                    method,
+                    rng: StdRng::seed_from_u64(seed),
+                    spawned: 0,
+                    expected: 0.0,
+                },
+                Text2d::default(),
+                TextFont::from_font_size(14.0),
+                TextColor(colour),
+                Anchor::CENTER_LEFT,
+                Transform::from_xyz(LABEL_X, y, 0.0),
+                RenderLayers::layer(RENDER_LAYER),
+            ),
+        );
+    }
+}
+
+fn move_blips<T: TimestepComponent>(
+    mut commands: Commands,
+    mut blips: Query<(Entity, &mut Blip, &mut SimulationTransform, &mut Sprite), With<T>>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut blip, mut transform, mut sprite) in blips.iter_mut() {
+        blip.age += dt;
+        if blip.age > LIFETIME {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation.x += BLIP_SPEED * dt;
+        sprite.color.set_alpha(1.0 - (blip.age / LIFETIME));
+    }
+}
+
+fn spawn_blips<T: TimestepComponent>(
+    mut commands: Commands,
+    mut spawners: Query<(&mut Spawner, &Transform), With<T>>,
+    config: Res<StochasticConfig>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+    let mean = config.rate * dt;
+
+    for (mut spawner, transform) in spawners.iter_mut() {
+        let spawner = &mut *spawner;
+        spawner.expected += mean;
+
+        let count = match spawner.method {
+            Method::PerUpdate => {
+                (spawner.rng.random::<f32>() < config.rate / REFERENCE_RATE) as u32
+            }
+            Method::ScaledByDelta => (spawner.rng.random::<f32>() < mean) as u32,
+            Method::Poisson => Poisson::new(mean)
+                .map(|poisson| poisson.sample(&mut spawner.rng) as u32)
+                .unwrap_or(0),
+        };
+        spawner.spawned += count;
+
+        let colour: Color = spawner.timestep.palette().sample_unchecked(0.0).into();
+        let y = transform.translation.y;
+        for _ in 0..count {
+            commands.spawn_with_timestep(
+                &spawner.timestep,
+                (
+                    Blip { age: 0.0 },
+                    SimulationTransform::from_xyz(SPAWNER_X, y, 1.0),
+                    RenderLayers::layer(RENDER_LAYER),
+                    Sprite::from_color(colour, Vec2::splat(8.0)),
+                ),
+            );
+        }
+    }
+}
+
+fn update_labels(mut spawners: Query<(&mut Text2d, &Spawner)>) {
+    for (mut text, spawner) in spawners.iter_mut() {
+        let percentage = if spawner.expected > 0.0 {
+            (spawner.spawned as f32) / spawner.expected * 100.0
+        } else {
+            100.0
+        };
+        text.0 = format!(
+            "{} {}: {} of {:.0} ({percentage:.0}%)",
+            spawner.timestep.name(),
+            spawner.method.name(),
+            spawner.spawned,
+            spawner.expected,
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use std::f32::consts::TAU;
+
+use bevy::{
+    camera::visibility::RenderLayers,
+    ecs::{
+        schedule::ScheduleConfigs,
+        system::{ScheduleSystem, SystemId},
+    },
+    prelude::*,
+    sprite::Anchor,
+};
+
+use crate::{
+    configuration::{
+        AppExt, CommandsExt, DespawnSystems, SimulationMeta, Timestep, TimestepComponent,
+        TimesteppedSystems,
+    },
+    interpolation::SimulationTransform,
+};
+
+#[derive(Resource)]
+pub struct WheelsMeta {
+    pub camera: Entity,
+    pub spawn: SystemId<In<Timestep>>,
+}
+
+impl SimulationMeta for WheelsMeta {
+    fn get(&self) -> (Entity, SystemId<In<Timestep>>) {
+        (self.camera, self.spawn)
+    }
+}
+
+/// Clockwise revolutions per second of every wheel
+#[derive(Resource, Clone, Copy, PartialEq, Debug)]
+pub struct WheelSpeed(pub f32);
+
+impl Default for WheelSpeed {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+pub const MIN_WHEEL_SPEED: f32 = 0.0;
+pub const MAX_WHEEL_SPEED: f32 = 40.0;
+
+#[derive(Component)]
+struct Wheel {
+    timestep: Timestep,
+    /// Angle turned by the most recent update, in radians
+    step: f32,
+}
+
+#[derive(Component)]
+struct WheelLabel(Entity);
+
+#[derive(GizmoConfigGroup, Default, Reflect)]
+struct WheelGizmos;
+
+/// Every spoke looks identical, so the wheel looks the same after turning by any multiple of `TAU / SPOKES`
+const SPOKES: usize = 8;
+const RADIUS: f32 = 70.0;
+const SPACING: f32 = 170.0;
+
+const RENDER_LAYER: usize = 10;
+
+struct Systems;
+
+impl TimesteppedSystems for Systems {
+    fn get_systems_for_timestep<T: TimestepComponent>() -> ScheduleConfigs<ScheduleSystem> {
+        run::<T>.into_configs()
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<WheelSpeed>()
+        .init_gizmo_group::<WheelGizmos>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, update_labels)
+        .add_systems(PostUpdate, draw.after(TransformSystems::Propagate))
+        .add_systems_with_timestep::<Systems>();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut despawns: ResMut<DespawnSystems>,
+    mut gizmos: ResMut<GizmoConfigStore>,
+) {
+    let despawn = commands.register_system(despawn);
+    despawns.0.push(despawn);
+    let spawn = commands.register_system(spawn);
+
+    let camera = commands.spawn_simulation_camera(RENDER_LAYER).id();
+    commands.insert_resource(WheelsMeta { camera, spawn });
+
+    gizmos.config_mut::<WheelGizmos>().0.render_layers = RenderLayers::layer(RENDER_LAYER);
+}
+
+fn despawn(mut commands: Commands, entities: Query<Entity, Or<(With<Wheel>, With<WheelLabel>)>>) {
+    for entity in entities.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn spawn(timestep: In<Timestep>, mut commands: Commands) {
+    let colour: Color = timestep.palette().sample_unchecked(0.0).into();
+    let x = ((timestep.index() as f32) * SPACING) - 250.0;
+
+    let wheel = commands
+        .spawn_with_timestep(
+            &timestep.0,
+            (
+                Wheel {
+                    timestep: timestep.0,
+                    step: 0.0,
+                },
+                SimulationTransform::from_xyz(x, 0.0, 1.0),
+                Visibility::default(),
+                RenderLayers::layer(RENDER_LAYER),
+            ),
+        )
+        .with_children(|wheel| {
+            for i in 0..SPOKES {
+                let rotation = Quat::from_rotation_z((i as f32) * TAU / (SPOKES as f32));
+                wheel.spawn((
+                    Transform::from_rotation(rotation),
+                    RenderLayers::layer(RENDER_LAYER),
+                    Sprite::from_color(colour, Vec2::new(RADIUS, 4.0)),
+                    Anchor::CENTER_LEFT,
+                ));
+            }
+        })
+        .id();
+
+    commands.spawn((
+        WheelLabel(wheel),
+        Text2d::default(),
+        TextFont::from_font_size(16.0),
+        TextColor(colour),
+        Anchor::TOP_CENTER,
+        Transform::from_xyz(x, -RADIUS - 12.0, 0.0),
+        RenderLayers::layer(RENDER_LAYER),
+    ));
+}
+
+fn run<T: TimestepComponent>(
+    mut wheels: Query<(&mut Wheel, &mut SimulationTransform), With<T>>,
+    speed: Res<WheelSpeed>,
+    time: Res<Time>,
+) {
+    let step = speed.0 * TAU * time.delta_secs();
+
+    for (mut wheel, mut transform) in wheels.iter_mut() {
+        wheel.step = step;
+        transform.rotate_z(-step);
+    }
+}
+
+fn draw(wheels: Query<(&Wheel, &GlobalTransform)>, mut gizmos: Gizmos<WheelGizmos>) {
+    for (wheel, transform) in wheels.iter() {
+        let colour = wheel.timestep.palette().sample_unchecked(0.0);
+        gizmos.circle_2d(transform.translation().truncate(), RADIUS, colour);
+    }
+}
+
+fn update_labels(mut labels: Query<(&mut Text2d, &WheelLabel)>, wheels: Query<&Wheel>) {
+    for (mut text, label) in labels.iter_mut() {
+        let Ok(wheel) = wheels.get(label.0) else {
+            continue;
+        };
+
+        // Beyond half a turn per update, interpolation takes the shortest path and spins the wheel backwards
+        text.0 = format!(
+            "{}\n{:.1}° per update\n{:.2} spokes per update",
+            wheel.timestep.name(),
+            wheel.step.to_degrees(),
+            wheel.step * (SPOKES as f32) / TAU,
+        );
+    }
+}
@@ -1,10 +1,37 @@
 use std::time::Duration;
 
-use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+use bevy::{
+    app::{FixedMain, RunFixedMainLoopSystems},
+    ecs::schedule::ScheduleLabel,
+    prelude::*,
+    time::{TimeSystems, common_conditions::paused},
+};
+
+use crate::configuration::Timestep;
 
 #[derive(Resource)]
 pub struct SimulationDelta(pub Duration);
 
+/// Pauses every timestep, and allows stepping through them while paused
+#[derive(Resource, Default)]
+pub struct Pause {
+    pub paused: bool,
+    /// Run the next render frame as if unpaused. Only set while paused
+    pub step_frame: bool,
+    /// Run a single simulation update of the given timestep on the next frame.
+    /// Only set while paused, as it would otherwise add an extra update to a running frame
+    pub step_tick: Option<Timestep>,
+}
+
+/// Tracks when `Time<Virtual>` clamps the frame delta to its `max_delta`
+#[derive(Resource, Default)]
+pub struct DeltaClamping {
+    /// Whether the delta was clamped on the current frame
+    pub clamped: bool,
+    /// How many frames have been clamped in total
+    pub count: u32,
+}
+
 #[derive(Component, ScheduleLabel, Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
 pub struct NoDelta;
 
@@ -19,17 +46,117 @@ pub struct Fixed;
 
 pub fn plugin(app: &mut App) {
     app.insert_resource(SimulationDelta(Duration::from_secs_f32(1.0 / 64.0)))
+        .init_resource::<Pause>()
+        .init_resource::<DeltaClamping>()
         .init_resource::<Time<NoDelta>>()
         .init_resource::<Time<SemiFixed>>()
+        .add_systems(
+            First,
+            (
+                update_pause.before(TimeSystems),
+                detect_delta_clamping.after(TimeSystems),
+            ),
+        )
         .add_systems(
             Update,
-            (update_fixed_delta, no_delta, variable_delta, semi_fixed),
+            (
+                update_fixed_delta,
+                (no_delta, variable_delta, semi_fixed).run_if(not(paused)),
+                step_tick,
+            )
+                .chain(),
+        )
+        .add_systems(
+            RunFixedMainLoop,
+            step_fixed_tick.in_set(RunFixedMainLoopSystems::AfterFixedMainLoop),
         )
         .add_systems(FixedUpdate, fixed);
 }
 
+/// Pausing is implemented through virtual time, so that the Fixed timestep is paused by Bevy itself
+fn update_pause(mut pause: ResMut<Pause>, mut time: ResMut<Time<Virtual>>) {
+    if pause.paused && !pause.step_frame {
+        time.pause();
+    } else {
+        time.unpause();
+    }
+    pause.step_frame = false;
+    // Drop any step that was requested just before unpausing, rather than running it on the next pause
+    if !pause.paused {
+        pause.step_tick = None;
+    }
+}
+
+/// Mirrors the check in `Time<Virtual>::advance_with_raw_delta`
+fn detect_delta_clamping(
+    mut clamping: ResMut<DeltaClamping>,
+    real: Res<Time<Real>>,
+    time: Res<Time<Virtual>>,
+) {
+    let clamped = !time.is_paused() && real.delta() > time.max_delta();
+    if clamped {
+        clamping.count += 1;
+    }
+    // Avoid triggering change detection every frame
+    if clamping.clamped != clamped {
+        clamping.clamped = clamped;
+    }
+}
+
+/// Runs a single update of the requested timestep, with a delta time of the configured [`SimulationDelta`]
+fn step_tick(world: &mut World) {
+    let mut pause = world.resource_mut::<Pause>();
+    if !pause.paused {
+        return;
+    }
+    let timestep = match pause.step_tick {
+        // Fixed is handled separately, in the fixed main loop
+        Some(Timestep::Fixed) | None => return,
+        Some(timestep) => timestep,
+    };
+    pause.step_tick = None;
+
+    match timestep {
+        Timestep::NoDelta => no_delta(world),
+        Timestep::VariableDelta => {
+            let delta = world.resource::<SimulationDelta>().0;
+            world.resource_mut::<Time>().advance_by(delta);
+            world.run_schedule(VariableDelta);
+            *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+        }
+        Timestep::SemiFixed => {
+            let delta = world.resource::<SimulationDelta>().0;
+            let mut time = world.resource_mut::<Time<SemiFixed>>();
+            time.advance_by(delta);
+            *world.resource_mut::<Time>() = time.as_generic();
+            world.run_schedule(SemiFixed);
+            *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+        }
+        Timestep::Fixed => unreachable!("Handled by step_fixed_tick"),
+    }
+}
+
+/// Same as a single iteration of Bevy's `run_fixed_main_schedule`,
+/// but without needing any time to have been accumulated
+fn step_fixed_tick(world: &mut World) {
+    let mut pause = world.resource_mut::<Pause>();
+    if !pause.paused || !matches!(pause.step_tick, Some(Timestep::Fixed)) {
+        return;
+    }
+    pause.step_tick = None;
+
+    let mut time = world.resource_mut::<Time<bevy::prelude::Fixed>>();
+    let timestep = time.timestep();
+    time.advance_by(timestep);
+    *world.resource_mut::<Time>() = time.as_generic();
+    world.run_schedule(FixedMain);
+    *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+}
+
 fn no_delta(world: &mut World) {
-    let delta = world.resource::<SimulationDelta>().0;
+    // Every other timestep is scaled through `Time<Virtual>`, but this one has to be scaled manually
+    let speed = world.resource::<Time<Virtual>>().relative_speed_f64();
+    let delta = world.resource::<SimulationDelta>().0.mul_f64(speed);
     let mut time = world.resource_mut::<Time<NoDelta>>();
     time.advance_by(delta);
 
@@ -1,21 +1,43 @@
+use std::time::Duration;
+
 use bevy::{
     feathers::{self, dark_theme::create_dark_theme, theme::UiTheme},
     input_focus::tab_navigation::TabGroup,
     prelude::*,
+    ui::Checked,
+    ui_widgets::SliderValue,
 };
 
+mod input;
+mod lorenz_attractor;
 mod presentation_modes;
+mod presets;
+mod shortcuts;
 mod simulation;
 mod tabs;
+mod text_field;
 mod timesteps;
 mod update_rate;
 
-use crate::ui::{
-    presentation_modes::presentation_modes,
-    simulation::simulation,
-    tabs::{TabCorners, tabs},
-    timesteps::timesteps,
-    update_rate::update_rate,
+pub use {
+    presentation_modes::FullscreenMode,
+    simulation::LagConfig,
+    timesteps::{MAX_MAX_DELTA_MS, MAX_TIME_SCALE, MIN_TIME_SCALE},
+    update_rate::{CachedWinitSettings, UpdateModeVariant},
+};
+
+use crate::{
+    configuration::respawn,
+    ui::{
+        input::input,
+        lorenz_attractor::lorenz_attractor,
+        presentation_modes::presentation_modes,
+        presets::presets,
+        simulation::simulation,
+        tabs::{TabCorners, tabs},
+        timesteps::timesteps,
+        update_rate::update_rate,
+    },
 };
 
 const GAP_SIZE: Val = Val::Px(12.0);
@@ -23,7 +45,7 @@ const MAX_WIDTH: Val = Val::Px(720.0);
 
 /// Largest range sliders can have without skipping over some integers.
 /// Equal to `(MAX_WIDTH / 2.0) + (GAP_SIZE * 2.0)`
-const SLIDER_PRECISION: f32 = 336.0;
+pub const SLIDER_PRECISION: f32 = 336.0;
 
 /// Add a description below a node
 fn describe(node: impl Bundle, description: impl Into<String>) -> impl Bundle {
@@ -39,14 +61,79 @@ fn describe(node: impl Bundle, description: impl Into<String>) -> impl Bundle {
     )
 }
 
+/// Check or uncheck a widget to match the configuration it represents,
+/// for when that configuration was changed by something other than the widget itself
+fn sync_checked(commands: &mut Commands, entity: Entity, is_checked: bool, checked: bool) {
+    if is_checked == checked {
+        return;
+    }
+
+    if checked {
+        commands.entity(entity).insert(Checked);
+    } else {
+        commands.entity(entity).remove::<Checked>();
+    }
+}
+
+/// Move a slider to match the configuration it represents,
+/// for when that configuration was changed by something other than the slider itself
+fn sync_slider(commands: &mut Commands, (entity, value): (Entity, &SliderValue), new_value: f32) {
+    if value.0 != new_value {
+        commands.entity(entity).insert(SliderValue(new_value));
+    }
+}
+
+/// How long a slider has to rest before a change that needs a respawn takes effect,
+/// so that dragging it doesn't respawn every entity on each step
+const RESPAWN_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// A respawn waiting for the slider that asked for it to stop moving
+#[derive(Resource, Default)]
+struct PendingRespawn(Option<Timer>);
+
+impl PendingRespawn {
+    /// Respawn once no more requests have been made for [`RESPAWN_DEBOUNCE`]
+    fn request(&mut self) {
+        self.0 = Some(Timer::new(RESPAWN_DEBOUNCE, TimerMode::Once));
+    }
+}
+
+/// Uses real time, so that pending respawns still happen while paused
+fn run_pending_respawn(
+    mut commands: Commands,
+    mut pending: ResMut<PendingRespawn>,
+    time: Res<Time<Real>>,
+) {
+    let Some(timer) = pending.bypass_change_detection().0.as_mut() else {
+        return;
+    };
+
+    if timer.tick(time.delta()).is_finished() {
+        pending.0 = None;
+        commands.run_system_cached(respawn);
+    }
+}
+
 #[derive(Component, Default)]
 struct TopLevelTabs;
 
 pub fn plugin(app: &mut App) {
-    app.add_plugins((update_rate::plugin, simulation::plugin))
-        .insert_resource(UiTheme(create_dark_theme()))
-        .insert_resource(ClearColor(feathers::palette::GRAY_0))
-        .add_systems(Startup, setup);
+    app.add_plugins((
+        update_rate::plugin,
+        input::plugin,
+        lorenz_attractor::plugin,
+        simulation::plugin,
+        timesteps::plugin,
+        presentation_modes::plugin,
+        presets::plugin,
+        shortcuts::plugin,
+        text_field::plugin,
+    ))
+    .init_resource::<PendingRespawn>()
+    .insert_resource(UiTheme(create_dark_theme()))
+    .insert_resource(ClearColor(feathers::palette::GRAY_0))
+    .add_systems(Startup, setup)
+    .add_systems(Update, run_pending_respawn);
 }
 
 fn setup(mut commands: Commands) {
@@ -55,8 +142,11 @@ fn setup(mut commands: Commands) {
         TabCorners::Top,
         ("Simulation", simulation()),
         ("Timesteps", timesteps()),
+        ("Input", input()),
+        ("Lorenz", lorenz_attractor()),
         ("Presentation Modes", presentation_modes()),
         ("Update Rate", update_rate()),
+        ("Presets", presets()),
     ];
 
     commands.spawn((
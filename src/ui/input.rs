@@ -0,0 +1,144 @@
+use bevy::{
+    ecs::{schedule::ScheduleConfigs, system::ScheduleSystem},
+    feathers::controls::{ButtonProps, button, checkbox},
+    input::InputSystems,
+    prelude::*,
+    ui::Checked,
+    ui_widgets::{Activate, ValueChange, observe},
+};
+
+use crate::{
+    configuration::{AppExt, Timestep, TimestepComponent, TimesteppedSystems},
+    input_buffer::InputBuffer,
+    ui::{describe, sync_checked},
+};
+
+/// Key presses counted for the demo
+#[derive(Resource, Default)]
+struct PressCounts {
+    presses: u32,
+    /// Indexed by [`Timestep::index`]
+    seen: [u32; 4],
+}
+
+#[derive(Component)]
+struct InputBufferCheckbox;
+
+#[derive(Component)]
+struct SeenPressesText(Timestep);
+
+struct CountSeenPresses;
+
+impl TimesteppedSystems for CountSeenPresses {
+    fn get_systems_for_timestep<T: TimestepComponent>() -> ScheduleConfigs<ScheduleSystem> {
+        count_seen_presses::<T>.into_configs()
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PressCounts>()
+        .add_systems_with_timestep::<CountSeenPresses>()
+        .add_systems(PreUpdate, count_presses.after(InputSystems))
+        .add_systems(
+            Update,
+            (
+                sync_input_buffer_checkbox.run_if(resource_changed::<InputBuffer>),
+                update_seen_presses.run_if(resource_changed::<PressCounts>),
+            ),
+        );
+}
+
+fn seen_presses_text(timestep: Timestep) -> impl Bundle {
+    (
+        Text::default(),
+        TextFont::from_font_size(18.0),
+        SeenPressesText(timestep),
+    )
+}
+
+pub fn input() -> impl Bundle {
+    children![
+        describe(
+            checkbox(
+                (
+                    Checked,
+                    InputBufferCheckbox,
+                    observe(
+                        |on: On<ValueChange<bool>>,
+                         mut buffer: ResMut<InputBuffer>,
+                         mut commands: Commands| {
+                            buffer.enabled = on.value;
+                            if on.value {
+                                commands.entity(on.source).insert(Checked);
+                            } else {
+                                commands.entity(on.source).remove::<Checked>();
+                            }
+                        }
+                    )
+                ),
+                Spawn(Text::new("Buffer Input"))
+            ),
+            "Hands every key press to exactly one update of each timestep. When off, updates read the presses from the current render frame, so timesteps that update zero or more times per frame will miss presses or see them twice."
+        ),
+        describe(
+            Text::new("Key Presses Seen:"),
+            "Press any keys, and compare how many presses each timestep saw. Lower the Simulation Rate or add lag to make the difference obvious."
+        ),
+        (
+            Node {
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            children![
+                seen_presses_text(Timestep::NoDelta),
+                seen_presses_text(Timestep::VariableDelta),
+                seen_presses_text(Timestep::SemiFixed),
+                seen_presses_text(Timestep::Fixed),
+            ],
+        ),
+        button(
+            ButtonProps::default(),
+            observe(|_: On<Activate>, mut counts: ResMut<PressCounts>| {
+                *counts = default();
+            }),
+            Spawn(Text::new("Reset Counts"))
+        ),
+    ]
+}
+
+fn count_presses(mut counts: ResMut<PressCounts>, keys: Res<ButtonInput<KeyCode>>) {
+    let presses = keys.get_just_pressed().len() as u32;
+    if presses > 0 {
+        counts.presses += presses;
+    }
+}
+
+fn count_seen_presses<T: TimestepComponent>(
+    mut counts: ResMut<PressCounts>,
+    buffer: Res<InputBuffer>,
+) {
+    let seen = buffer.get_just_pressed(T::TIMESTEP).count() as u32;
+    if seen > 0 {
+        counts.seen[T::TIMESTEP.index()] += seen;
+    }
+}
+
+fn sync_input_buffer_checkbox(
+    mut commands: Commands,
+    checkbox: Single<(Entity, Has<Checked>), With<InputBufferCheckbox>>,
+    buffer: Res<InputBuffer>,
+) {
+    let (entity, is_checked) = *checkbox;
+    sync_checked(&mut commands, entity, is_checked, buffer.enabled);
+}
+
+fn update_seen_presses(mut texts: Query<(&mut Text, &SeenPressesText)>, counts: Res<PressCounts>) {
+    for (mut text, seen) in &mut texts {
+        text.0 = format!(
+            "{}: {} of {}",
+            seen.0.name(),
+            counts.seen[seen.0.index()],
+            counts.presses
+        );
+    }
+}
@@ -0,0 +1,437 @@
+use bevy::{
+    feathers::{
+        self,
+        controls::{ButtonProps, SliderProps, button, checkbox, radio, slider},
+    },
+    prelude::*,
+    ui::Checked,
+    ui_widgets::{Activate, RadioGroup, SliderPrecision, SliderValue, ValueChange, observe},
+};
+use clap::ValueEnum;
+
+use crate::{
+    configuration::{ActiveSimulation, Timestep, respawn},
+    simulation::{
+        DIVERGENCE_WINDOW, DivergenceReference, LORENZ_PRESETS, LorenzConfig, LorenzDivergence,
+        MAX_BETA, MAX_RHO, MAX_SIGMA, MAX_START, MAX_TRAIL_LENGTH, MIN_BETA, MIN_RHO, MIN_SIGMA,
+        MIN_TRAIL_LENGTH,
+    },
+    ui::{GAP_SIZE, PendingRespawn, describe, sync_checked, sync_slider},
+};
+
+/// Which value of [`LorenzConfig`] a slider controls
+#[derive(Component, Clone, Copy)]
+enum LorenzSlider {
+    Sigma,
+    Rho,
+    Beta,
+    /// Axis of the starting point
+    Start(usize),
+}
+
+impl LorenzSlider {
+    fn value_mut(self, config: &mut LorenzConfig) -> &mut f64 {
+        match self {
+            Self::Sigma => &mut config.sigma,
+            Self::Rho => &mut config.rho,
+            Self::Beta => &mut config.beta,
+            Self::Start(axis) => &mut config.start[axis],
+        }
+    }
+
+    fn value(self, config: &LorenzConfig) -> f64 {
+        match self {
+            Self::Sigma => config.sigma,
+            Self::Rho => config.rho,
+            Self::Beta => config.beta,
+            Self::Start(axis) => config.start[axis],
+        }
+    }
+}
+
+#[derive(Component)]
+struct OffsetStartsCheckbox;
+
+#[derive(Component)]
+struct TrailLengthSlider;
+
+#[derive(Component)]
+struct DivergenceReferenceRadio(DivergenceReference);
+
+/// One of the dots that make up a timestep's line on the divergence graph
+#[derive(Component)]
+struct GraphPoint {
+    timestep: Timestep,
+    column: usize,
+}
+
+#[derive(Component)]
+struct LyapunovLabel;
+
+/// Dots per line of the divergence graph
+const GRAPH_COLUMNS: usize = 150;
+const GRAPH_HEIGHT: Val = Val::Px(150.0);
+/// Base 10 logarithms of the distances at the bottom and top of the divergence graph
+const GRAPH_RANGE: (f64, f64) = (-9.0, 2.0);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            sync_widgets.run_if(resource_changed::<LorenzConfig>),
+            (update_graph, update_lyapunov_label).run_if(resource_changed::<LorenzDivergence>),
+        ),
+    );
+}
+
+/// Restart the trajectories so that a new starting point takes effect, unless another simulation is showing
+fn respawn_if_active(commands: &mut Commands, active_simulation: ActiveSimulation) {
+    if active_simulation == ActiveSimulation::LorenzAttractor {
+        commands.run_system_cached(respawn);
+    }
+}
+
+fn lorenz_slider(kind: LorenzSlider, min: f32, max: f32, precision: i32) -> impl Bundle {
+    slider(
+        SliderProps {
+            value: kind.value(&LorenzConfig::default()) as f32,
+            min,
+            max,
+        },
+        (
+            kind,
+            SliderPrecision(precision),
+            observe(
+                move |on: On<ValueChange<f32>>,
+                      mut commands: Commands,
+                      mut config: ResMut<LorenzConfig>,
+                      active_simulation: Res<ActiveSimulation>| {
+                    commands.entity(on.source).insert(SliderValue(on.value));
+                    *kind.value_mut(&mut config) = on.value as f64;
+                    // The parameters apply to the running trajectories, but the starting point only applies to new ones
+                    if let LorenzSlider::Start(_) = kind {
+                        respawn_if_active(&mut commands, *active_simulation);
+                    }
+                },
+            ),
+        ),
+    )
+}
+
+fn labelled(label: &'static str, node: impl Bundle) -> impl Bundle {
+    (
+        Node {
+            column_gap: GAP_SIZE,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        children![Text::new(label), node],
+    )
+}
+
+fn preset_button((name, sigma, rho, beta): (&'static str, f64, f64, f64)) -> impl Bundle {
+    button(
+        ButtonProps::default(),
+        observe(
+            move |_: On<Activate>,
+                  mut commands: Commands,
+                  mut config: ResMut<LorenzConfig>,
+                  active_simulation: Res<ActiveSimulation>| {
+                config.sigma = sigma;
+                config.rho = rho;
+                config.beta = beta;
+                // Clear the old trails, so that only the new regime is shown
+                respawn_if_active(&mut commands, *active_simulation);
+            },
+        ),
+        Spawn(Text::new(name)),
+    )
+}
+
+fn reference_radios() -> impl Bundle {
+    let radio = |reference: DivergenceReference, name: &'static str| {
+        radio(DivergenceReferenceRadio(reference), Spawn(Text::new(name)))
+    };
+
+    (
+        Node {
+            column_gap: GAP_SIZE,
+            row_gap: GAP_SIZE,
+            flex_wrap: FlexWrap::Wrap,
+            ..default()
+        },
+        RadioGroup,
+        observe(
+            |on: On<ValueChange<Entity>>,
+             radios: Query<(Entity, &DivergenceReferenceRadio)>,
+             mut config: ResMut<LorenzConfig>,
+             active_simulation: Res<ActiveSimulation>,
+             mut commands: Commands| {
+                for (entity, radio) in radios.iter() {
+                    if entity == on.value {
+                        commands.entity(entity).insert(Checked);
+                        config.reference = radio.0;
+                        // Restart the measurements, so that they're all against the same reference
+                        respawn_if_active(&mut commands, *active_simulation);
+                    } else {
+                        commands.entity(entity).remove::<Checked>();
+                    }
+                }
+            },
+        ),
+        children![
+            radio(DivergenceReference::HighRate, "High-Rate Run"),
+            radio(
+                DivergenceReference::Timestep(Timestep::NoDelta),
+                Timestep::NoDelta.name()
+            ),
+            radio(
+                DivergenceReference::Timestep(Timestep::VariableDelta),
+                Timestep::VariableDelta.name()
+            ),
+            radio(
+                DivergenceReference::Timestep(Timestep::SemiFixed),
+                Timestep::SemiFixed.name()
+            ),
+            radio(
+                DivergenceReference::Timestep(Timestep::Fixed),
+                Timestep::Fixed.name()
+            ),
+        ],
+    )
+}
+
+fn graph_point(timestep: Timestep, column: usize) -> impl Bundle {
+    (
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Px(2.0),
+            height: Val::Px(2.0),
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(timestep.palette().sample_unchecked(0.0).into()),
+        GraphPoint { timestep, column },
+    )
+}
+
+fn divergence_graph() -> impl Bundle {
+    (
+        Node {
+            height: GRAPH_HEIGHT,
+            border: UiRect::all(Val::Px(1.0)),
+            ..default()
+        },
+        BackgroundColor(feathers::palette::GRAY_0),
+        BorderColor::all(feathers::palette::WARM_GRAY_1),
+        Children::spawn(SpawnIter(Timestep::value_variants().iter().flat_map(
+            |&timestep| (0..GRAPH_COLUMNS).map(move |column| graph_point(timestep, column)),
+        ))),
+    )
+}
+
+pub fn lorenz_attractor() -> impl Bundle {
+    children![
+        describe(
+            Text::new("Parameters:"),
+            "Sigma, rho and beta of the Lorenz system. Changes apply to the trajectories immediately."
+        ),
+        labelled(
+            "Sigma",
+            lorenz_slider(LorenzSlider::Sigma, MIN_SIGMA, MAX_SIGMA, 1)
+        ),
+        labelled("Rho", lorenz_slider(LorenzSlider::Rho, MIN_RHO, MAX_RHO, 2)),
+        labelled(
+            "Beta",
+            lorenz_slider(LorenzSlider::Beta, MIN_BETA, MAX_BETA, 2)
+        ),
+        describe(
+            Text::new("Presets:"),
+            "Well-known regimes of the Lorenz system. Larger values of rho grow the attractor beyond the view."
+        ),
+        (
+            Node {
+                column_gap: GAP_SIZE,
+                row_gap: GAP_SIZE,
+                flex_wrap: FlexWrap::Wrap,
+                ..default()
+            },
+            Children::spawn(SpawnIter(LORENZ_PRESETS.into_iter().map(preset_button))),
+        ),
+        describe(
+            Text::new("Starting Point:"),
+            "Where every trajectory starts. Changes restart the simulation."
+        ),
+        labelled(
+            "x",
+            lorenz_slider(LorenzSlider::Start(0), -MAX_START, MAX_START, 1)
+        ),
+        labelled(
+            "y",
+            lorenz_slider(LorenzSlider::Start(1), -MAX_START, MAX_START, 1)
+        ),
+        labelled(
+            "z",
+            lorenz_slider(LorenzSlider::Start(2), -MAX_START, MAX_START, 1)
+        ),
+        describe(
+            checkbox(
+                (
+                    OffsetStartsCheckbox,
+                    observe(
+                        |on: On<ValueChange<bool>>,
+                         mut commands: Commands,
+                         mut config: ResMut<LorenzConfig>,
+                         active_simulation: Res<ActiveSimulation>| {
+                            config.offset_starts = on.value;
+                            respawn_if_active(&mut commands, *active_simulation);
+                        }
+                    ),
+                ),
+                Spawn(Text::new("Offset Each Timestep's Start"))
+            ),
+            "Start each timestep a millionth of a unit apart, to show how quickly tiny differences in initial conditions grow."
+        ),
+        describe(
+            Text::new("Trail Length:"),
+            "Line segments kept in each trajectory's trail, before the oldest are overwritten. Changes restart the simulation."
+        ),
+        slider(
+            SliderProps {
+                value: LorenzConfig::default().trail_length as f32,
+                min: MIN_TRAIL_LENGTH as f32,
+                max: MAX_TRAIL_LENGTH as f32,
+            },
+            (
+                TrailLengthSlider,
+                SliderPrecision(0),
+                observe(
+                    |on: On<ValueChange<f32>>,
+                     mut commands: Commands,
+                     mut config: ResMut<LorenzConfig>,
+                     mut pending: ResMut<PendingRespawn>,
+                     active_simulation: Res<ActiveSimulation>| {
+                        commands.entity(on.source).insert(SliderValue(on.value));
+                        config.trail_length = on.value as u32;
+                        // The trails are only sized when spawned
+                        if *active_simulation == ActiveSimulation::LorenzAttractor {
+                            pending.request();
+                        }
+                    }
+                ),
+            ),
+        ),
+        describe(
+            Text::new("Divergence:"),
+            format!(
+                "Distance of each timestep's trajectory from the reference, compared every frame at the same moment of virtual time. \
+                Graphed over the last {DIVERGENCE_WINDOW:.0} seconds, on a log scale from 1e{:.0} to 1e{:.0}.",
+                GRAPH_RANGE.0, GRAPH_RANGE.1
+            )
+        ),
+        reference_radios(),
+        divergence_graph(),
+        describe(
+            (Text::default(), LyapunovLabel),
+            "Estimated from how quickly each distance grows before reaching the size of the attractor. About 0.91 for the Chaotic preset."
+        ),
+    ]
+}
+
+fn sync_widgets(
+    mut commands: Commands,
+    sliders: Query<(Entity, &SliderValue, &LorenzSlider)>,
+    trail_slider: Single<(Entity, &SliderValue), With<TrailLengthSlider>>,
+    checkbox: Single<(Entity, Has<Checked>), With<OffsetStartsCheckbox>>,
+    radios: Query<(Entity, Has<Checked>, &DivergenceReferenceRadio)>,
+    config: Res<LorenzConfig>,
+) {
+    for (entity, value, &kind) in sliders.iter() {
+        sync_slider(&mut commands, (entity, value), kind.value(&config) as f32);
+    }
+    sync_slider(&mut commands, *trail_slider, config.trail_length as f32);
+    let (entity, is_checked) = *checkbox;
+    sync_checked(&mut commands, entity, is_checked, config.offset_starts);
+    for (entity, is_checked, radio) in radios.iter() {
+        sync_checked(
+            &mut commands,
+            entity,
+            is_checked,
+            radio.0 == config.reference,
+        );
+    }
+}
+
+fn update_graph(mut points: Query<(&mut Node, &GraphPoint)>, divergence: Res<LorenzDivergence>) {
+    // Scroll along once the graph is full
+    let start = (divergence.elapsed - DIVERGENCE_WINDOW).max(0.0);
+
+    for (mut node, point) in points.iter_mut() {
+        let time = start + (DIVERGENCE_WINDOW * (point.column as f64) / (GRAPH_COLUMNS as f64));
+        let sample = divergence
+            .timesteps
+            .iter()
+            .find(|entry| entry.timestep == point.timestep)
+            .filter(|_| time <= divergence.elapsed)
+            .and_then(|entry| {
+                // The latest sample at or before this column's time
+                let index = entry
+                    .samples
+                    .partition_point(|&(sample_time, _)| sample_time <= time);
+                index.checked_sub(1).map(|index| entry.samples[index])
+            });
+
+        let Some((_, distance)) = sample else {
+            node.display = Display::None;
+            continue;
+        };
+
+        let (bottom, top) = GRAPH_RANGE;
+        let height = ((distance.log10() - bottom) / (top - bottom)).clamp(0.0, 1.0);
+        node.display = Display::Flex;
+        node.left = Val::Percent(100.0 * (point.column as f32) / (GRAPH_COLUMNS as f32));
+        node.bottom = Val::Percent(100.0 * height as f32);
+    }
+}
+
+fn update_lyapunov_label(
+    mut label: Single<&mut Text, With<LyapunovLabel>>,
+    divergence: Res<LorenzDivergence>,
+    config: Res<LorenzConfig>,
+) {
+    if divergence.timesteps.is_empty() {
+        label.0 =
+            "Lyapunov exponent: only measured while the Lorenz Attractor simulation is active"
+                .to_string();
+        return;
+    }
+    if let DivergenceReference::Timestep(reference) = config.reference
+        && !divergence
+            .timesteps
+            .iter()
+            .any(|entry| entry.timestep == reference)
+    {
+        label.0 = format!(
+            "Lyapunov exponent: the reference timestep, {}, isn't active",
+            reference.name()
+        );
+        return;
+    }
+
+    let lines = divergence.timesteps.iter().map(|entry| {
+        let estimate = if config.reference == DivergenceReference::Timestep(entry.timestep) {
+            "reference".to_string()
+        } else {
+            entry
+                .lyapunov_exponent()
+                .map_or("not enough growth yet".to_string(), |exponent| {
+                    format!("{exponent:.2}")
+                })
+        };
+        format!("{}: {estimate}", entry.timestep.name())
+    });
+    label.0 = format!(
+        "Lyapunov exponent:\n{}",
+        lines.collect::<Vec<_>>().join("\n")
+    );
+}
@@ -3,16 +3,18 @@ use bevy::{
     prelude::*,
     ui::Checked,
     ui_widgets::{RadioGroup, ValueChange, observe},
-    window::{PresentMode, WindowMode},
+    window::{PresentMode, PrimaryWindow, WindowMode},
 };
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
 
-use crate::ui::{GAP_SIZE, describe};
+use crate::ui::{GAP_SIZE, describe, sync_checked};
 
 #[derive(Component)]
 struct WindowPresentMode(PresentMode);
 
-#[derive(Component, Clone, Copy)]
-enum FullscreenMode {
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize, ValueEnum)]
+pub enum FullscreenMode {
     Windowed,
     BorderlessFullscreen,
     Fullscreen,
@@ -32,6 +34,20 @@ impl From<FullscreenMode> for WindowMode {
     }
 }
 
+impl From<WindowMode> for FullscreenMode {
+    fn from(mode: WindowMode) -> Self {
+        match mode {
+            WindowMode::Windowed => Self::Windowed,
+            WindowMode::BorderlessFullscreen(_) => Self::BorderlessFullscreen,
+            WindowMode::Fullscreen(_, _) => Self::Fullscreen,
+        }
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, sync_radios);
+}
+
 pub fn presentation_modes() -> impl Bundle {
     children![
         (
@@ -149,3 +165,24 @@ pub fn presentation_modes() -> impl Bundle {
         )
     ]
 }
+
+fn sync_radios(
+    mut commands: Commands,
+    window: Single<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+    present_modes: Query<(Entity, Has<Checked>, &WindowPresentMode)>,
+    fullscreen_modes: Query<(Entity, Has<Checked>, &FullscreenMode)>,
+) {
+    for (entity, is_checked, mode) in present_modes.iter() {
+        sync_checked(
+            &mut commands,
+            entity,
+            is_checked,
+            mode.0 == window.present_mode,
+        );
+    }
+
+    let window_mode = FullscreenMode::from(window.mode);
+    for (entity, is_checked, &mode) in fullscreen_modes.iter() {
+        sync_checked(&mut commands, entity, is_checked, mode == window_mode);
+    }
+}
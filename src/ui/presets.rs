@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+
+use bevy::{
+    feathers::{
+        controls::{ButtonProps, button},
+        palette,
+        rounded_corners::RoundedCorners,
+    },
+    prelude::*,
+    ui_widgets::{Activate, observe},
+};
+
+use crate::{
+    configuration::respawn,
+    settings::{Settings, load_config, save_config},
+    ui::{
+        GAP_SIZE, describe,
+        text_field::{TextField, text_field},
+    },
+};
+
+const PRESETS_FILE: &str = "presets.ron";
+
+/// Saved configurations, by name
+#[derive(Resource, Default)]
+struct Presets(BTreeMap<String, Settings>);
+
+/// The most recently saved or loaded preset
+#[derive(Resource, Default)]
+struct SelectedPreset(Option<String>);
+
+#[derive(Component)]
+struct PresetNameField;
+
+#[derive(Component)]
+struct PresetDropdownLabel;
+
+#[derive(Component)]
+struct PresetList;
+
+#[derive(Component)]
+struct PresetOption(String);
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(Presets(load_config(PRESETS_FILE).unwrap_or_default()))
+        .init_resource::<SelectedPreset>()
+        .add_systems(
+            Update,
+            (
+                rebuild_preset_list.run_if(resource_changed::<Presets>),
+                update_selected_preset.run_if(resource_changed::<SelectedPreset>),
+            ),
+        );
+}
+
+pub fn presets() -> impl Bundle {
+    children![
+        describe(
+            Text::new("Presets:"),
+            "Save every setting in the app under a name, and restore them later."
+        ),
+        (
+            Node {
+                column_gap: GAP_SIZE,
+                ..default()
+            },
+            children![
+                text_field(PresetNameField),
+                button(
+                    ButtonProps::default(),
+                    observe(save_preset),
+                    Spawn(Text::new("Save"))
+                ),
+                button(
+                    ButtonProps::default(),
+                    observe(delete_preset),
+                    Spawn(Text::new("Delete"))
+                ),
+            ],
+        ),
+        (
+            Node {
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            children![
+                button(
+                    ButtonProps::default(),
+                    observe(toggle_preset_list),
+                    Spawn((Text::new("Load Preset..."), PresetDropdownLabel))
+                ),
+                (
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        border: UiRect::all(Val::Px(1.0)).with_top(Val::ZERO),
+                        display: Display::None,
+                        ..default()
+                    },
+                    BorderColor::all(palette::WARM_GRAY_1),
+                    PresetList,
+                ),
+            ],
+        ),
+    ]
+}
+
+fn save_preset(
+    _: On<Activate>,
+    name: Single<&TextField, With<PresetNameField>>,
+    mut selected: ResMut<SelectedPreset>,
+    mut commands: Commands,
+) {
+    let name = name.0.trim().to_owned();
+    if name.is_empty() {
+        return;
+    }
+
+    selected.0 = Some(name.clone());
+    commands.queue(move |world: &mut World| {
+        let settings = Settings::capture(world);
+        let mut presets = world.resource_mut::<Presets>();
+        presets.0.insert(name, settings);
+        save_config(PRESETS_FILE, &presets.0);
+    });
+}
+
+fn delete_preset(
+    _: On<Activate>,
+    name: Single<&TextField, With<PresetNameField>>,
+    mut presets: ResMut<Presets>,
+    mut selected: ResMut<SelectedPreset>,
+) {
+    let name = name.0.trim();
+    if presets.0.remove(name).is_none() {
+        return;
+    }
+
+    save_config(PRESETS_FILE, &presets.0);
+    if selected.0.as_deref() == Some(name) {
+        selected.0 = None;
+    }
+}
+
+fn toggle_preset_list(_: On<Activate>, mut list: Single<&mut Node, With<PresetList>>) {
+    list.display = match list.display {
+        Display::None => Display::Flex,
+        _ => Display::None,
+    };
+}
+
+fn load_preset(
+    on: On<Activate>,
+    options: Query<&PresetOption>,
+    mut list: Single<&mut Node, With<PresetList>>,
+    presets: Res<Presets>,
+    mut selected: ResMut<SelectedPreset>,
+    mut commands: Commands,
+) {
+    let Ok(option) = options.get(on.entity) else {
+        return;
+    };
+    let Some(settings) = presets.0.get(&option.0).cloned() else {
+        return;
+    };
+
+    list.display = Display::None;
+    selected.0 = Some(option.0.clone());
+    commands.queue(move |world: &mut World| settings.apply(world));
+    commands.run_system_cached(respawn);
+}
+
+fn rebuild_preset_list(
+    mut commands: Commands,
+    list: Single<Entity, With<PresetList>>,
+    presets: Res<Presets>,
+) {
+    let mut list = commands.entity(*list);
+    list.despawn_related::<Children>();
+
+    if presets.0.is_empty() {
+        list.with_child((
+            Node {
+                padding: UiRect::all(Val::Px(4.0)),
+                ..default()
+            },
+            Text::new("No presets saved yet"),
+            TextFont::from_font_size(18.0),
+        ));
+        return;
+    }
+
+    for name in presets.0.keys() {
+        list.with_child(button(
+            ButtonProps {
+                corners: RoundedCorners::None,
+                ..default()
+            },
+            (PresetOption(name.clone()), observe(load_preset)),
+            Spawn(Text::new(name.clone())),
+        ));
+    }
+}
+
+fn update_selected_preset(
+    mut label: Single<&mut Text, With<PresetDropdownLabel>>,
+    mut name: Single<&mut TextField, With<PresetNameField>>,
+    selected: Res<SelectedPreset>,
+) {
+    match &selected.0 {
+        Some(selected) => {
+            label.0 = format!("Preset: {selected}");
+            name.0.clone_from(selected);
+        }
+        None => label.0 = "Load Preset...".to_owned(),
+    }
+}
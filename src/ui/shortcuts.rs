@@ -0,0 +1,219 @@
+//! Keyboard equivalents of the UI widgets, for changing the configuration without moving the mouse.
+
+use std::time::Duration;
+
+use bevy::{
+    input_focus::InputFocus,
+    prelude::*,
+    window::{PresentMode, PrimaryWindow},
+};
+
+use crate::{
+    configuration::{ActiveSimulation, ActiveTimesteps, Timestep, respawn},
+    input_buffer::InputBuffer,
+    interpolation::InterpolationMode,
+    timestep::{Pause, SimulationDelta},
+    ui::{LagConfig, MAX_TIME_SCALE, MIN_TIME_SCALE, SLIDER_PRECISION, text_field::TextField},
+};
+
+const SIMULATION_KEYS: [(KeyCode, ActiveSimulation); 10] = [
+    (KeyCode::Digit1, ActiveSimulation::LorenzAttractor),
+    (KeyCode::Digit2, ActiveSimulation::MouseCursor),
+    (KeyCode::Digit3, ActiveSimulation::MovingBars),
+    (KeyCode::Digit4, ActiveSimulation::Character),
+    (KeyCode::Digit5, ActiveSimulation::BouncingBalls),
+    (KeyCode::Digit6, ActiveSimulation::SpringChain),
+    (KeyCode::Digit7, ActiveSimulation::Orbit),
+    (KeyCode::Digit8, ActiveSimulation::Projectiles),
+    (KeyCode::Digit9, ActiveSimulation::CameraFollow),
+    (KeyCode::Digit0, ActiveSimulation::Smoothing),
+];
+
+/// Held with shift, for the simulations beyond the first ten
+const SHIFTED_SIMULATION_KEYS: [(KeyCode, ActiveSimulation); 7] = [
+    (KeyCode::Digit1, ActiveSimulation::Wheels),
+    (KeyCode::Digit2, ActiveSimulation::Fountain),
+    (KeyCode::Digit3, ActiveSimulation::Rope),
+    (KeyCode::Digit4, ActiveSimulation::RapidFire),
+    (KeyCode::Digit5, ActiveSimulation::Stochastic),
+    (KeyCode::Digit6, ActiveSimulation::Keyframes),
+    (KeyCode::Digit7, ActiveSimulation::Boids),
+];
+
+const TIMESTEP_KEYS: [(KeyCode, Timestep); 4] = [
+    (KeyCode::F1, Timestep::NoDelta),
+    (KeyCode::F2, Timestep::VariableDelta),
+    (KeyCode::F3, Timestep::SemiFixed),
+    (KeyCode::F4, Timestep::Fixed),
+];
+
+/// In the same order as the UI
+const PRESENT_MODES: [PresentMode; 6] = [
+    PresentMode::AutoVsync,
+    PresentMode::AutoNoVsync,
+    PresentMode::Fifo,
+    PresentMode::FifoRelaxed,
+    PresentMode::Immediate,
+    PresentMode::Mailbox,
+];
+
+/// Used when toggling lag on, if it wasn't previously configured
+const DEFAULT_LAG: LagConfig = LagConfig {
+    frames_delay: 30,
+    lag_duration_ms: 100,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            switch_simulation,
+            toggle_timesteps,
+            change_simulation_rate,
+            change_time_scale,
+            cycle_interpolation_mode,
+            cycle_present_mode,
+            toggle_lag,
+            toggle_input_buffering,
+            pause,
+        )
+            .run_if(not(typing_into_text_field)),
+    );
+}
+
+fn typing_into_text_field(focus: Res<InputFocus>, text_fields: Query<(), With<TextField>>) -> bool {
+    focus.0.is_some_and(|entity| text_fields.contains(entity))
+}
+
+fn switch_simulation(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut active_simulation: ResMut<ActiveSimulation>,
+    mut commands: Commands,
+) {
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let simulation_keys = if shift {
+        &SHIFTED_SIMULATION_KEYS[..]
+    } else {
+        &SIMULATION_KEYS[..]
+    };
+
+    for &(key, simulation) in simulation_keys {
+        if keys.just_pressed(key) {
+            *active_simulation = simulation;
+            commands.run_system_cached(respawn);
+        }
+    }
+}
+
+/// Holding shift steps the timestep instead of toggling it
+fn toggle_timesteps(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut active_timesteps: ResMut<ActiveTimesteps>,
+    mut pause: ResMut<Pause>,
+    mut commands: Commands,
+) {
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    for (key, timestep) in TIMESTEP_KEYS {
+        if !keys.just_pressed(key) {
+            continue;
+        }
+
+        if shift {
+            if pause.paused {
+                pause.step_tick = Some(timestep);
+            }
+        } else {
+            active_timesteps.toggle(timestep.into());
+            commands.run_system_cached(respawn);
+        }
+    }
+}
+
+fn change_simulation_rate(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut simulation_delta: ResMut<SimulationDelta>,
+) {
+    let increase = keys.any_just_pressed([KeyCode::Equal, KeyCode::NumpadAdd]);
+    let decrease = keys.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]);
+    let change = match (increase, decrease) {
+        (true, false) => 1.0,
+        (false, true) => -1.0,
+        _ => return,
+    };
+
+    let rate = simulation_delta.0.as_secs_f32().recip().round();
+    let rate = (rate + change).clamp(1.0, SLIDER_PRECISION);
+    simulation_delta.0 = Duration::from_secs_f32(rate.recip());
+}
+
+fn change_time_scale(keys: Res<ButtonInput<KeyCode>>, mut time: ResMut<Time<Virtual>>) {
+    let factor = match (
+        keys.just_pressed(KeyCode::BracketRight),
+        keys.just_pressed(KeyCode::BracketLeft),
+    ) {
+        (true, false) => 2.0,
+        (false, true) => 0.5,
+        _ => return,
+    };
+
+    let speed = (time.relative_speed() * factor).clamp(MIN_TIME_SCALE, MAX_TIME_SCALE);
+    time.set_relative_speed(speed);
+}
+
+fn cycle_interpolation_mode(keys: Res<ButtonInput<KeyCode>>, mut mode: ResMut<InterpolationMode>) {
+    if !keys.just_pressed(KeyCode::KeyI) {
+        return;
+    }
+
+    *mode = match *mode {
+        InterpolationMode::None => InterpolationMode::Interpolate,
+        InterpolationMode::Interpolate => InterpolationMode::Extrapolate,
+        InterpolationMode::Extrapolate => InterpolationMode::None,
+    };
+}
+
+fn cycle_present_mode(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+) {
+    if !keys.just_pressed(KeyCode::KeyP) {
+        return;
+    }
+
+    let index = PRESENT_MODES
+        .iter()
+        .position(|&mode| mode == window.present_mode)
+        .map_or(0, |index| (index + 1) % PRESENT_MODES.len());
+    window.present_mode = PRESENT_MODES[index];
+}
+
+fn toggle_lag(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut config: ResMut<LagConfig>,
+    mut previous: Local<Option<LagConfig>>,
+) {
+    if !keys.just_pressed(KeyCode::KeyL) {
+        return;
+    }
+
+    if config.lag_duration_ms == 0 {
+        *config = previous.take().unwrap_or(DEFAULT_LAG);
+    } else {
+        *previous = Some(std::mem::take(&mut *config));
+    }
+}
+
+fn toggle_input_buffering(keys: Res<ButtonInput<KeyCode>>, mut buffer: ResMut<InputBuffer>) {
+    if keys.just_pressed(KeyCode::KeyB) {
+        buffer.enabled = !buffer.enabled;
+    }
+}
+
+fn pause(keys: Res<ButtonInput<KeyCode>>, mut pause: ResMut<Pause>) {
+    if keys.just_pressed(KeyCode::Space) {
+        pause.paused = !pause.paused;
+    }
+    if keys.just_pressed(KeyCode::Period) && pause.paused {
+        pause.step_frame = true;
+    }
+}
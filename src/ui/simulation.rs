@@ -1,3 +1,11 @@
+mod boids;
+mod camera_follow;
+mod fountain;
+mod rapid_fire;
+mod spring_chain;
+mod stochastic;
+mod wheels;
+
 use std::time::Duration;
 
 use bevy::{
@@ -6,28 +14,82 @@ use bevy::{
     ui::Checked,
     ui_widgets::{RadioGroup, SliderPrecision, SliderValue, ValueChange, observe},
 };
+use serde::{Deserialize, Serialize};
 
 use crate::{
     configuration::{ActiveSimulation, respawn},
-    ui::{SLIDER_PRECISION, describe},
+    ui::{
+        GAP_SIZE, SLIDER_PRECISION, describe,
+        simulation::{
+            boids::boids, camera_follow::camera_follow, fountain::fountain, rapid_fire::rapid_fire,
+            spring_chain::spring_chain, stochastic::stochastic, wheels::wheels,
+        },
+        sync_checked, sync_slider,
+    },
 };
 
 #[derive(Component)]
 struct SimulationRadioButton(ActiveSimulation);
 
-#[derive(Resource, Default)]
-struct LagConfig {
-    frames_delay: u32,
-    lag_duration_ms: u64,
+/// Settings that only apply to one simulation, only shown while it's active
+#[derive(Component)]
+struct SimulationPanel(ActiveSimulation);
+
+#[derive(Component)]
+struct LagFrequencySlider;
+
+#[derive(Component)]
+struct LagDurationSlider;
+
+#[derive(Resource, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LagConfig {
+    pub frames_delay: u32,
+    pub lag_duration_ms: u64,
 }
 
 pub(super) fn plugin(app: &mut App) {
-    app.init_resource::<LagConfig>()
-        .add_systems(Update, lag_system);
+    app.add_plugins((
+        spring_chain::plugin,
+        camera_follow::plugin,
+        wheels::plugin,
+        fountain::plugin,
+        rapid_fire::plugin,
+        stochastic::plugin,
+        boids::plugin,
+    ))
+    .init_resource::<LagConfig>()
+    .add_systems(
+        Update,
+        (
+            lag_system,
+            (sync_simulation_radios, sync_simulation_panels)
+                .run_if(resource_changed::<ActiveSimulation>),
+            sync_lag_sliders.run_if(resource_changed::<LagConfig>),
+        ),
+    );
 }
 
-pub fn simulation() -> impl Bundle {
+fn panel(simulation: ActiveSimulation, contents: impl Bundle) -> impl Bundle {
+    (
+        Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: GAP_SIZE,
+            display: Display::None,
+            ..default()
+        },
+        SimulationPanel(simulation),
+        contents,
+    )
+}
+
+fn simulation_radios() -> impl Bundle {
     (
+        Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: GAP_SIZE,
+            ..default()
+        },
         RadioGroup,
         observe(
             |on: On<ValueChange<Entity>>,
@@ -72,57 +134,208 @@ pub fn simulation() -> impl Bundle {
                 "High-contrast vertical bars, useful for visualising screen tearing and stuttering."
             ),
             describe(
-                Text::new("Artificial Lag:"),
-                "Fake a heavier computational load to manually slow the app down."
-            ),
-            describe(
-                Text::new("Lag Frequency"),
-                "How many frames to wait between each lag frame."
-            ),
-            slider(
-                SliderProps {
-                    value: 0.0,
-                    min: 0.0,
-                    max: SLIDER_PRECISION
-                },
-                (
-                    SliderPrecision(0),
-                    observe(
-                        |on: On<ValueChange<f32>>,
-                         mut commands: Commands,
-                         mut config: ResMut<LagConfig>| {
-                            commands.entity(on.source).insert(SliderValue(on.value));
-                            config.frames_delay = on.value as u32;
-                        }
-                    )
-                ),
-            ),
-            describe(
-                Text::new("Lag Duration"),
-                "Extra time in milliseconds to wait for, on each lag frame."
-            ),
-            slider(
-                SliderProps {
-                    value: 0.0,
-                    min: 0.0,
-                    max: SLIDER_PRECISION
-                },
-                (
-                    SliderPrecision(0),
-                    observe(
-                        |on: On<ValueChange<f32>>,
-                         mut commands: Commands,
-                         mut config: ResMut<LagConfig>| {
-                            commands.entity(on.source).insert(SliderValue(on.value));
-                            config.lag_duration_ms = on.value as u64;
-                        }
-                    )
+                radio(
+                    SimulationRadioButton(ActiveSimulation::Character),
+                    Spawn(Text::new("Character"))
+                ),
+                "A character moved with A and D, that jumps with W. Useful for comparing input latency, missed jumps and jump heights."
+            ),
+            describe(
+                radio(
+                    SimulationRadioButton(ActiveSimulation::BouncingBalls),
+                    Spawn(Text::new("Bouncing Balls"))
+                ),
+                "Fast balls in a box with thin walls. Large delta time values let them pass straight through the walls."
+            ),
+            describe(
+                radio(
+                    SimulationRadioButton(ActiveSimulation::SpringChain),
+                    Spawn(Text::new("Spring Chain"))
+                ),
+                "A chain of masses and springs, integrated with explicit Euler. Explodes whenever the delta time is too large for its stiffness."
+            ),
+            describe(
+                radio(
+                    SimulationRadioButton(ActiveSimulation::Orbit),
+                    Spawn(Text::new("Orbit"))
+                ),
+                "A star and a planet orbiting each other, with graphs of how far their energy and angular momentum have drifted from their initial values, and how far the orbit has precessed."
+            ),
+            describe(
+                radio(
+                    SimulationRadioButton(ActiveSimulation::Projectiles),
+                    Spawn(Text::new("Projectiles"))
+                ),
+                "Identical shots launched over and over, with a scatter plot of how high and how far each one went."
+            ),
+            describe(
+                radio(
+                    SimulationRadioButton(ActiveSimulation::CameraFollow),
+                    Spawn(Text::new("Camera Follow"))
+                ),
+                "A camera following a moving target, with a graph of how much the target jitters on screen. Uses the timesteps chosen below, rather than the active ones."
+            ),
+            describe(
+                radio(
+                    SimulationRadioButton(ActiveSimulation::Smoothing),
+                    Spawn(Text::new("Smoothing"))
+                ),
+                "Naive lerp and velocity damping, which settle at different speeds depending on the update rate, alongside their framerate-independent equivalents."
+            ),
+            describe(
+                radio(
+                    SimulationRadioButton(ActiveSimulation::Wheels),
+                    Spawn(Text::new("Wheels"))
+                ),
+                "Spinning spoked wheels, which appear to slow down, stop or reverse whenever they turn close to a whole number of spokes per update or per frame."
+            ),
+            describe(
+                radio(
+                    SimulationRadioButton(ActiveSimulation::Fountain),
+                    Spawn(Text::new("Fountain"))
+                ),
+                "Particle fountains that naively emit a whole number of particles each update, next to ones that carry the remainder over and spread each update's particles out. Raise the emission rate to use it as a load generator."
+            ),
+            describe(
+                radio(
+                    SimulationRadioButton(ActiveSimulation::Rope),
+                    Spawn(Text::new("Rope"))
+                ),
+                "Ropes hanging from the mouse cursor, integrated with Verlet and held together by constraint iterations. Stretch and jitter whenever the delta time varies."
+            ),
+            describe(
+                radio(
+                    SimulationRadioButton(ActiveSimulation::RapidFire),
+                    Spawn(Text::new("Rapid Fire"))
+                ),
+                "Guns firing on a repeating timer, with a count of the shots fired against the shots expected. Checking whether the timer just finished fires at most once per update, and loses shots whenever the fire rate is above the update rate."
+            ),
+            describe(
+                radio(
+                    SimulationRadioButton(ActiveSimulation::Stochastic),
+                    Spawn(Text::new("Stochastic"))
+                ),
+                "Random events aiming for the same average rate, rolled with a fixed probability per update, a probability scaled by delta time, or a Poisson draw. Only the Poisson draw stays accurate at every update rate. A seed only repeats the same events with No Delta and Fixed, as the other timesteps draw against frame timings."
+            ),
+            describe(
+                radio(
+                    SimulationRadioButton(ActiveSimulation::Keyframes),
+                    Spawn(Text::new("Keyframes"))
+                ),
+                "A looping keyframed animation, advanced by each update's delta time. The outlines show where it should be according to virtual time, alongside how far each animation has drifted from them."
+            ),
+            describe(
+                radio(
+                    SimulationRadioButton(ActiveSimulation::Boids),
+                    Spawn(Text::new("Boids"))
                 ),
+                "Flocks of boids that take longer to update the bigger they are, with each update's cost shown alongside. A realistic load for pushing Semi-Fixed and Fixed into a death spiral."
             ),
         ],
     )
 }
 
+pub fn simulation() -> impl Bundle {
+    children![
+        simulation_radios(),
+        panel(ActiveSimulation::SpringChain, spring_chain()),
+        panel(ActiveSimulation::CameraFollow, camera_follow()),
+        panel(ActiveSimulation::Wheels, wheels()),
+        panel(ActiveSimulation::Fountain, fountain()),
+        panel(ActiveSimulation::RapidFire, rapid_fire()),
+        panel(ActiveSimulation::Stochastic, stochastic()),
+        panel(ActiveSimulation::Boids, boids()),
+        describe(
+            Text::new("Artificial Lag:"),
+            "Fake a heavier computational load to manually slow the app down."
+        ),
+        describe(
+            Text::new("Lag Frequency"),
+            "How many frames to wait between each lag frame."
+        ),
+        slider(
+            SliderProps {
+                value: 0.0,
+                min: 0.0,
+                max: SLIDER_PRECISION
+            },
+            (
+                LagFrequencySlider,
+                SliderPrecision(0),
+                observe(
+                    |on: On<ValueChange<f32>>,
+                     mut commands: Commands,
+                     mut config: ResMut<LagConfig>| {
+                        commands.entity(on.source).insert(SliderValue(on.value));
+                        config.frames_delay = on.value as u32;
+                    }
+                )
+            ),
+        ),
+        describe(
+            Text::new("Lag Duration"),
+            "Extra time in milliseconds to wait for, on each lag frame."
+        ),
+        slider(
+            SliderProps {
+                value: 0.0,
+                min: 0.0,
+                max: SLIDER_PRECISION
+            },
+            (
+                LagDurationSlider,
+                SliderPrecision(0),
+                observe(
+                    |on: On<ValueChange<f32>>,
+                     mut commands: Commands,
+                     mut config: ResMut<LagConfig>| {
+                        commands.entity(on.source).insert(SliderValue(on.value));
+                        config.lag_duration_ms = on.value as u64;
+                    }
+                )
+            ),
+        ),
+    ]
+}
+
+fn sync_simulation_radios(
+    mut commands: Commands,
+    radios: Query<(Entity, Has<Checked>, &SimulationRadioButton)>,
+    active_simulation: Res<ActiveSimulation>,
+) {
+    for (entity, is_checked, simulation) in radios.iter() {
+        sync_checked(
+            &mut commands,
+            entity,
+            is_checked,
+            simulation.0 == *active_simulation,
+        );
+    }
+}
+
+fn sync_simulation_panels(
+    mut panels: Query<(&mut Node, &SimulationPanel)>,
+    active_simulation: Res<ActiveSimulation>,
+) {
+    for (mut node, panel) in panels.iter_mut() {
+        node.display = if panel.0 == *active_simulation {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+fn sync_lag_sliders(
+    mut commands: Commands,
+    frequency: Single<(Entity, &SliderValue), With<LagFrequencySlider>>,
+    duration: Single<(Entity, &SliderValue), With<LagDurationSlider>>,
+    config: Res<LagConfig>,
+) {
+    sync_slider(&mut commands, *frequency, config.frames_delay as f32);
+    sync_slider(&mut commands, *duration, config.lag_duration_ms as f32);
+}
+
 fn lag_system(config: Res<LagConfig>, mut counter: Local<u32>) {
     if *counter >= config.frames_delay {
         *counter = 0;
@@ -0,0 +1,63 @@
+use bevy::{
+    feathers::controls::{SliderProps, slider},
+    prelude::*,
+    ui_widgets::{SliderPrecision, SliderValue, ValueChange, observe},
+};
+
+use crate::{
+    configuration::ActiveSimulation,
+    simulation::{BoidCount, MAX_BOID_COUNT, MIN_BOID_COUNT},
+    ui::{PendingRespawn, describe, sync_slider},
+};
+
+#[derive(Component)]
+struct BoidCountSlider;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        sync_boid_count_slider.run_if(resource_changed::<BoidCount>),
+    );
+}
+
+pub(super) fn boids() -> impl Bundle {
+    children![
+        describe(
+            Text::new("Boid Count:"),
+            "Boids in each flock of the Boids simulation."
+        ),
+        slider(
+            SliderProps {
+                value: BoidCount::default().0 as f32,
+                min: MIN_BOID_COUNT as f32,
+                max: MAX_BOID_COUNT as f32,
+            },
+            (
+                BoidCountSlider,
+                SliderPrecision(0),
+                observe(
+                    |on: On<ValueChange<f32>>,
+                     mut commands: Commands,
+                     mut count: ResMut<BoidCount>,
+                     mut pending: ResMut<PendingRespawn>,
+                     active_simulation: Res<ActiveSimulation>| {
+                        commands.entity(on.source).insert(SliderValue(on.value));
+                        count.0 = on.value as u32;
+                        // The flocks are only sized when spawned
+                        if *active_simulation == ActiveSimulation::Boids {
+                            pending.request();
+                        }
+                    }
+                )
+            ),
+        ),
+    ]
+}
+
+fn sync_boid_count_slider(
+    mut commands: Commands,
+    slider: Single<(Entity, &SliderValue), With<BoidCountSlider>>,
+    count: Res<BoidCount>,
+) {
+    sync_slider(&mut commands, *slider, count.0 as f32);
+}
@@ -0,0 +1,148 @@
+use bevy::{
+    feathers::controls::{checkbox, radio},
+    prelude::*,
+    ui::Checked,
+    ui_widgets::{RadioGroup, ValueChange, observe},
+};
+
+use crate::{
+    configuration::{Timestep, respawn},
+    simulation::CameraFollowConfig,
+    ui::{GAP_SIZE, describe, sync_checked},
+};
+
+#[derive(Component)]
+struct CameraFollowRadio {
+    camera: bool,
+    timestep: Timestep,
+}
+
+#[derive(Component)]
+struct CameraFollowCheckbox {
+    camera: bool,
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        sync_camera_follow_widgets.run_if(resource_changed::<CameraFollowConfig>),
+    );
+}
+
+/// Choose which timestep drives either the camera or the target
+fn camera_follow_radios(camera: bool) -> impl Bundle {
+    let radio = move |timestep: Timestep| {
+        radio(
+            CameraFollowRadio { camera, timestep },
+            Spawn(Text::new(timestep.name())),
+        )
+    };
+
+    (
+        Node {
+            column_gap: GAP_SIZE,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        RadioGroup,
+        observe(
+            move |on: On<ValueChange<Entity>>,
+                  radios: Query<(Entity, &CameraFollowRadio)>,
+                  mut config: ResMut<CameraFollowConfig>,
+                  mut commands: Commands| {
+                for (entity, radio) in radios.iter().filter(|(_, radio)| radio.camera == camera) {
+                    if entity == on.value {
+                        commands.entity(entity).insert(Checked);
+                        if camera {
+                            config.camera = radio.timestep;
+                        } else {
+                            config.target = radio.timestep;
+                        }
+                        commands.run_system_cached(respawn);
+                    } else {
+                        commands.entity(entity).remove::<Checked>();
+                    }
+                }
+            },
+        ),
+        children![
+            Text::new(if camera { "Camera:" } else { "Target:" }),
+            radio(Timestep::NoDelta),
+            radio(Timestep::VariableDelta),
+            radio(Timestep::SemiFixed),
+            radio(Timestep::Fixed),
+        ],
+    )
+}
+
+fn camera_follow_checkbox(camera: bool, name: &'static str) -> impl Bundle {
+    checkbox(
+        (
+            CameraFollowCheckbox { camera },
+            observe(
+                move |on: On<ValueChange<bool>>,
+                      mut config: ResMut<CameraFollowConfig>,
+                      mut commands: Commands| {
+                    if camera {
+                        config.interpolate_camera = on.value;
+                    } else {
+                        config.interpolate_target = on.value;
+                    }
+                    commands.run_system_cached(respawn);
+                },
+            ),
+        ),
+        Spawn(Text::new(name)),
+    )
+}
+
+pub(super) fn camera_follow() -> impl Bundle {
+    children![
+        describe(
+            Text::new("Camera Follow:"),
+            "Which timesteps move the Camera Follow simulation's target and camera, and whether each of them is interpolated while using the Fixed timestep."
+        ),
+        camera_follow_radios(false),
+        camera_follow_radios(true),
+        (
+            Node {
+                column_gap: GAP_SIZE,
+                ..default()
+            },
+            children![
+                camera_follow_checkbox(false, "Interpolate Target"),
+                camera_follow_checkbox(true, "Interpolate Camera"),
+            ],
+        ),
+    ]
+}
+
+fn sync_camera_follow_widgets(
+    mut commands: Commands,
+    radios: Query<(Entity, Has<Checked>, &CameraFollowRadio)>,
+    checkboxes: Query<(Entity, Has<Checked>, &CameraFollowCheckbox)>,
+    config: Res<CameraFollowConfig>,
+) {
+    for (entity, is_checked, radio) in radios.iter() {
+        let timestep = if radio.camera {
+            config.camera
+        } else {
+            config.target
+        };
+        sync_checked(
+            &mut commands,
+            entity,
+            is_checked,
+            radio.timestep == timestep,
+        );
+    }
+
+    for (entity, is_checked, checkbox) in checkboxes.iter() {
+        let interpolate = if checkbox.camera {
+            config.interpolate_camera
+        } else {
+            config.interpolate_target
+        };
+        sync_checked(&mut commands, entity, is_checked, interpolate);
+    }
+}
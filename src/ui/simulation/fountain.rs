@@ -0,0 +1,56 @@
+use bevy::{
+    feathers::controls::{SliderProps, slider},
+    prelude::*,
+    ui_widgets::{SliderPrecision, SliderValue, ValueChange, observe},
+};
+
+use crate::{
+    simulation::{EmissionRate, MAX_EMISSION_RATE, MIN_EMISSION_RATE},
+    ui::{describe, sync_slider},
+};
+
+#[derive(Component)]
+struct EmissionRateSlider;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        sync_emission_rate_slider.run_if(resource_changed::<EmissionRate>),
+    );
+}
+
+pub(super) fn fountain() -> impl Bundle {
+    children![
+        describe(
+            Text::new("Emission Rate:"),
+            "Particles emitted per second by each fountain in the Fountain simulation."
+        ),
+        slider(
+            SliderProps {
+                value: EmissionRate::default().0,
+                min: MIN_EMISSION_RATE,
+                max: MAX_EMISSION_RATE,
+            },
+            (
+                EmissionRateSlider,
+                SliderPrecision(0),
+                observe(
+                    |on: On<ValueChange<f32>>,
+                     mut commands: Commands,
+                     mut rate: ResMut<EmissionRate>| {
+                        commands.entity(on.source).insert(SliderValue(on.value));
+                        rate.0 = on.value;
+                    }
+                )
+            ),
+        ),
+    ]
+}
+
+fn sync_emission_rate_slider(
+    mut commands: Commands,
+    slider: Single<(Entity, &SliderValue), With<EmissionRateSlider>>,
+    rate: Res<EmissionRate>,
+) {
+    sync_slider(&mut commands, *slider, rate.0);
+}
@@ -0,0 +1,79 @@
+use bevy::{
+    feathers::controls::{SliderProps, checkbox, slider},
+    prelude::*,
+    ui::Checked,
+    ui_widgets::{SliderPrecision, SliderValue, ValueChange, observe},
+};
+
+use crate::{
+    configuration::respawn,
+    simulation::{MAX_FIRE_RATE, MIN_FIRE_RATE, RapidFireConfig},
+    ui::{describe, sync_checked, sync_slider},
+};
+
+#[derive(Component)]
+struct FireRateSlider;
+
+#[derive(Component)]
+struct AccurateFireCheckbox;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        sync_rapid_fire_widgets.run_if(resource_changed::<RapidFireConfig>),
+    );
+}
+
+pub(super) fn rapid_fire() -> impl Bundle {
+    children![
+        describe(
+            Text::new("Fire Rate:"),
+            "Shots per second of the Rapid Fire simulation, and how it handles its timer finishing more than once per update."
+        ),
+        slider(
+            SliderProps {
+                value: RapidFireConfig::default().rate,
+                min: MIN_FIRE_RATE,
+                max: MAX_FIRE_RATE,
+            },
+            (
+                FireRateSlider,
+                SliderPrecision(0),
+                observe(
+                    |on: On<ValueChange<f32>>,
+                     mut commands: Commands,
+                     mut config: ResMut<RapidFireConfig>| {
+                        commands.entity(on.source).insert(SliderValue(on.value));
+                        config.rate = on.value;
+                    }
+                )
+            ),
+        ),
+        checkbox(
+            (
+                AccurateFireCheckbox,
+                observe(
+                    |on: On<ValueChange<bool>>,
+                     mut config: ResMut<RapidFireConfig>,
+                     mut commands: Commands| {
+                        config.accurate = on.value;
+                        // Restart the counts, so that they only reflect the new handling
+                        commands.run_system_cached(respawn);
+                    }
+                ),
+            ),
+            Spawn(Text::new("Count Every Timer Finish"))
+        ),
+    ]
+}
+
+fn sync_rapid_fire_widgets(
+    mut commands: Commands,
+    slider: Single<(Entity, &SliderValue), With<FireRateSlider>>,
+    checkbox: Single<(Entity, Has<Checked>), With<AccurateFireCheckbox>>,
+    config: Res<RapidFireConfig>,
+) {
+    sync_slider(&mut commands, *slider, config.rate);
+    let (entity, is_checked) = *checkbox;
+    sync_checked(&mut commands, entity, is_checked, config.accurate);
+}
@@ -0,0 +1,56 @@
+use bevy::{
+    feathers::controls::{SliderProps, slider},
+    prelude::*,
+    ui_widgets::{SliderPrecision, SliderValue, ValueChange, observe},
+};
+
+use crate::{
+    simulation::{MAX_STIFFNESS, MIN_STIFFNESS, SpringStiffness},
+    ui::{describe, sync_slider},
+};
+
+#[derive(Component)]
+struct SpringStiffnessSlider;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        sync_spring_stiffness_slider.run_if(resource_changed::<SpringStiffness>),
+    );
+}
+
+pub(super) fn spring_chain() -> impl Bundle {
+    children![
+        describe(
+            Text::new("Spring Stiffness:"),
+            "Stiffer springs lower the largest delta time that the Spring Chain can handle."
+        ),
+        slider(
+            SliderProps {
+                value: SpringStiffness::default().0,
+                min: MIN_STIFFNESS,
+                max: MAX_STIFFNESS,
+            },
+            (
+                SpringStiffnessSlider,
+                SliderPrecision(0),
+                observe(
+                    |on: On<ValueChange<f32>>,
+                     mut commands: Commands,
+                     mut stiffness: ResMut<SpringStiffness>| {
+                        commands.entity(on.source).insert(SliderValue(on.value));
+                        stiffness.0 = on.value;
+                    }
+                )
+            ),
+        ),
+    ]
+}
+
+fn sync_spring_stiffness_slider(
+    mut commands: Commands,
+    slider: Single<(Entity, &SliderValue), With<SpringStiffnessSlider>>,
+    stiffness: Res<SpringStiffness>,
+) {
+    sync_slider(&mut commands, *slider, stiffness.0);
+}
@@ -0,0 +1,56 @@
+use bevy::{
+    feathers::controls::{SliderProps, slider},
+    prelude::*,
+    ui_widgets::{SliderPrecision, SliderValue, ValueChange, observe},
+};
+
+use crate::{
+    simulation::{MAX_EVENT_RATE, MIN_EVENT_RATE, StochasticConfig},
+    ui::{describe, sync_slider},
+};
+
+#[derive(Component)]
+struct EventRateSlider;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        sync_event_rate_slider.run_if(resource_changed::<StochasticConfig>),
+    );
+}
+
+pub(super) fn stochastic() -> impl Bundle {
+    children![
+        describe(
+            Text::new("Event Rate:"),
+            "Average events per second of the Stochastic simulation."
+        ),
+        slider(
+            SliderProps {
+                value: StochasticConfig::default().rate,
+                min: MIN_EVENT_RATE,
+                max: MAX_EVENT_RATE,
+            },
+            (
+                EventRateSlider,
+                SliderPrecision(0),
+                observe(
+                    |on: On<ValueChange<f32>>,
+                     mut commands: Commands,
+                     mut config: ResMut<StochasticConfig>| {
+                        commands.entity(on.source).insert(SliderValue(on.value));
+                        config.rate = on.value;
+                    }
+                )
+            ),
+        ),
+    ]
+}
+
+fn sync_event_rate_slider(
+    mut commands: Commands,
+    slider: Single<(Entity, &SliderValue), With<EventRateSlider>>,
+    config: Res<StochasticConfig>,
+) {
+    sync_slider(&mut commands, *slider, config.rate);
+}
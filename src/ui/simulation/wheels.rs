@@ -0,0 +1,56 @@
+use bevy::{
+    feathers::controls::{SliderProps, slider},
+    prelude::*,
+    ui_widgets::{SliderPrecision, SliderValue, ValueChange, observe},
+};
+
+use crate::{
+    simulation::{MAX_WHEEL_SPEED, MIN_WHEEL_SPEED, WheelSpeed},
+    ui::{describe, sync_slider},
+};
+
+#[derive(Component)]
+struct WheelSpeedSlider;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        sync_wheel_speed_slider.run_if(resource_changed::<WheelSpeed>),
+    );
+}
+
+pub(super) fn wheels() -> impl Bundle {
+    children![
+        describe(
+            Text::new("Wheel Speed:"),
+            "Revolutions per second of the Wheels simulation."
+        ),
+        slider(
+            SliderProps {
+                value: WheelSpeed::default().0,
+                min: MIN_WHEEL_SPEED,
+                max: MAX_WHEEL_SPEED,
+            },
+            (
+                WheelSpeedSlider,
+                SliderPrecision(1),
+                observe(
+                    |on: On<ValueChange<f32>>,
+                     mut commands: Commands,
+                     mut speed: ResMut<WheelSpeed>| {
+                        commands.entity(on.source).insert(SliderValue(on.value));
+                        speed.0 = on.value;
+                    }
+                )
+            ),
+        ),
+    ]
+}
+
+fn sync_wheel_speed_slider(
+    mut commands: Commands,
+    slider: Single<(Entity, &SliderValue), With<WheelSpeedSlider>>,
+    speed: Res<WheelSpeed>,
+) {
+    sync_slider(&mut commands, *slider, speed.0);
+}
@@ -0,0 +1,102 @@
+//! Minimal single-line text input, as feathers doesn't provide one yet.
+
+use bevy::{
+    feathers::{constants::size, palette},
+    input::{ButtonState, keyboard::KeyboardInput},
+    input_focus::{FocusedInput, InputFocus, tab_navigation::TabIndex},
+    prelude::*,
+    ui_widgets::observe,
+};
+
+/// The current contents of a text field
+#[derive(Component, Default)]
+pub struct TextField(pub String);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, (update_text, update_border));
+}
+
+/// Template function to spawn a text field.
+///
+/// Typing while the field has input focus edits its contents. Enter or Escape releases focus.
+pub fn text_field(overrides: impl Bundle) -> impl Bundle {
+    (
+        Node {
+            height: size::ROW_HEIGHT,
+            padding: UiRect::axes(Val::Px(8.0), Val::ZERO),
+            border: UiRect::all(Val::Px(1.0)),
+            align_items: AlignItems::Center,
+            flex_grow: 1.0,
+            ..default()
+        },
+        TextField::default(),
+        TabIndex(0),
+        BackgroundColor(palette::GRAY_2),
+        BorderColor::all(palette::WARM_GRAY_1),
+        BorderRadius::all(Val::Px(4.0)),
+        observe(type_into_field),
+        overrides,
+        children![(Text::default(), TextFont::from_font_size(18.0))],
+    )
+}
+
+fn type_into_field(
+    mut on: On<FocusedInput<KeyboardInput>>,
+    mut fields: Query<&mut TextField>,
+    mut focus: ResMut<InputFocus>,
+) {
+    let Ok(mut field) = fields.get_mut(on.focused_entity) else {
+        return;
+    };
+
+    // Don't let key presses meant for the field leak out to any ancestors
+    on.propagate(false);
+
+    let input = &on.input;
+    if input.state == ButtonState::Released {
+        return;
+    }
+
+    match input.key_code {
+        KeyCode::Backspace => {
+            field.0.pop();
+        }
+        KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Escape => focus.0 = None,
+        _ => {
+            if let Some(text) = &input.text {
+                field.0.extend(text.chars().filter(|c| !c.is_control()));
+            }
+        }
+    }
+}
+
+fn update_text(
+    fields: Query<(&TextField, &Children), Changed<TextField>>,
+    mut texts: Query<&mut Text>,
+) {
+    for (field, children) in fields.iter() {
+        for &child in children {
+            if let Ok(mut text) = texts.get_mut(child) {
+                text.0.clone_from(&field.0);
+            }
+        }
+    }
+}
+
+fn update_border(
+    mut fields: Query<(Entity, &mut BorderColor), With<TextField>>,
+    focus: Res<InputFocus>,
+) {
+    if !focus.is_changed() {
+        return;
+    }
+
+    for (entity, mut border) in fields.iter_mut() {
+        let colour = if focus.0 == Some(entity) {
+            palette::ACCENT
+        } else {
+            palette::WARM_GRAY_1
+        };
+        *border = BorderColor::all(colour);
+    }
+}
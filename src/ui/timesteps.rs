@@ -2,19 +2,66 @@ use std::time::Duration;
 
 use bevy::{
     ecs::system::{IntoObserverSystem, ObserverSystem},
-    feathers::controls::{SliderProps, checkbox, radio, slider},
+    feathers::{
+        controls::{ButtonProps, SliderProps, button, checkbox, radio, slider},
+        palette,
+    },
     prelude::*,
-    ui::Checked,
-    ui_widgets::{RadioGroup, SliderPrecision, SliderValue, ValueChange, observe},
+    ui::{Checked, InteractionDisabled},
+    ui_widgets::{Activate, RadioGroup, SliderPrecision, SliderValue, ValueChange, observe},
 };
 
 use crate::{
-    configuration::{ActiveTimesteps, respawn},
+    configuration::{ActiveTimesteps, Timestep, respawn},
     interpolation::InterpolationMode,
-    timestep::SimulationDelta,
-    ui::{SLIDER_PRECISION, describe},
+    timestep::{DeltaClamping, Pause, SimulationDelta},
+    ui::{GAP_SIZE, SLIDER_PRECISION, describe, sync_checked, sync_slider},
 };
 
+pub const MIN_TIME_SCALE: f32 = 0.05;
+pub const MAX_TIME_SCALE: f32 = 2.0;
+
+#[derive(Component)]
+struct SimulationRateSlider;
+
+#[derive(Component)]
+struct TimestepCheckbox(ActiveTimesteps);
+
+#[derive(Component)]
+struct TimeScaleSlider;
+
+#[derive(Component)]
+struct PauseCheckbox;
+
+pub const MAX_MAX_DELTA_MS: f32 = 1000.0;
+
+#[derive(Component)]
+struct MaxDeltaSlider;
+
+#[derive(Component)]
+struct UnclampedCheckbox;
+
+#[derive(Component)]
+struct ClampingIndicator;
+
+#[derive(Component)]
+struct ClampingCountText;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            sync_simulation_rate_slider.run_if(resource_changed::<SimulationDelta>),
+            sync_timestep_checkboxes.run_if(resource_changed::<ActiveTimesteps>),
+            sync_interpolation_radios.run_if(resource_changed::<InterpolationMode>),
+            sync_pause_checkbox.run_if(resource_changed::<Pause>),
+            sync_time_scale_slider,
+            sync_max_delta_widgets,
+            update_clamping_indicator,
+        ),
+    );
+}
+
 fn toggle_timestep(timestep: ActiveTimesteps) -> impl ObserverSystem<ValueChange<bool>, ()> {
     IntoObserverSystem::into_system(
         move |on: On<ValueChange<bool>>,
@@ -31,6 +78,18 @@ fn toggle_timestep(timestep: ActiveTimesteps) -> impl ObserverSystem<ValueChange
     )
 }
 
+fn step_tick_button(timestep: Timestep) -> impl Bundle {
+    button(
+        ButtonProps::default(),
+        observe(move |_: On<Activate>, mut pause: ResMut<Pause>| {
+            if pause.paused {
+                pause.step_tick = Some(timestep);
+            }
+        }),
+        Spawn(Text::new(timestep.name())),
+    )
+}
+
 pub fn timesteps() -> impl Bundle {
     (
         RadioGroup,
@@ -61,6 +120,7 @@ pub fn timesteps() -> impl Bundle {
                     max: SLIDER_PRECISION
                 },
                 (
+                    SimulationRateSlider,
                     SliderPrecision(0),
                     observe(
                         |on: On<ValueChange<f32>>,
@@ -72,17 +132,111 @@ pub fn timesteps() -> impl Bundle {
                     )
                 ),
             ),
+            describe(
+                Text::new("Time Scale:"),
+                "Slow motion, by scaling virtual time. Variable Delta Time scales its delta, Semi-Fixed and Fixed take smaller or fewer steps per frame, and No Delta Time scales its fixed delta time value."
+            ),
+            slider(
+                SliderProps {
+                    value: 1.0,
+                    min: MIN_TIME_SCALE,
+                    max: MAX_TIME_SCALE,
+                },
+                (
+                    TimeScaleSlider,
+                    SliderPrecision(2),
+                    observe(
+                        |on: On<ValueChange<f32>>,
+                         mut commands: Commands,
+                         mut time: ResMut<Time<Virtual>>| {
+                            commands.entity(on.source).insert(SliderValue(on.value));
+                            time.set_relative_speed(on.value);
+                        }
+                    )
+                ),
+            ),
+            describe(
+                Text::new("Max Delta:"),
+                "Frame deltas longer than this many milliseconds are clamped by virtual time. Hides the worst of Variable Delta Time's instability, and limits how far Semi-Fixed and Fixed can fall behind."
+            ),
+            slider(
+                SliderProps {
+                    value: Time::<Virtual>::default().max_delta().as_secs_f32() * 1000.0,
+                    min: 1.0,
+                    max: MAX_MAX_DELTA_MS,
+                },
+                (
+                    MaxDeltaSlider,
+                    SliderPrecision(0),
+                    observe(
+                        |on: On<ValueChange<f32>>,
+                         mut commands: Commands,
+                         mut time: ResMut<Time<Virtual>>| {
+                            commands.entity(on.source).insert(SliderValue(on.value));
+                            time.set_max_delta(Duration::from_secs_f32(on.value / 1000.0));
+                        }
+                    )
+                ),
+            ),
+            (
+                Node {
+                    column_gap: GAP_SIZE,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                children![
+                    checkbox(
+                        (
+                            UnclampedCheckbox,
+                            observe(
+                                |on: On<ValueChange<bool>>,
+                                 slider: Single<&SliderValue, With<MaxDeltaSlider>>,
+                                 mut time: ResMut<Time<Virtual>>| {
+                                    let max_delta = if on.value {
+                                        Duration::MAX
+                                    } else {
+                                        Duration::from_secs_f32(slider.0 / 1000.0)
+                                    };
+                                    time.set_max_delta(max_delta);
+                                }
+                            )
+                        ),
+                        Spawn(Text::new("Unclamped"))
+                    ),
+                    (
+                        Node {
+                            width: Val::Px(18.0),
+                            height: Val::Px(18.0),
+                            ..default()
+                        },
+                        BorderRadius::MAX,
+                        BackgroundColor(palette::GRAY_3),
+                        ClampingIndicator,
+                    ),
+                    (
+                        Text::new("Clamped frames: 0"),
+                        TextFont::from_font_size(18.0),
+                        ClampingCountText,
+                    ),
+                ],
+            ),
             Text::new("Timestep Toggles:"),
             describe(
                 checkbox(
-                    observe(toggle_timestep(ActiveTimesteps::NO_DELTA)),
+                    (
+                        TimestepCheckbox(ActiveTimesteps::NO_DELTA),
+                        observe(toggle_timestep(ActiveTimesteps::NO_DELTA)),
+                    ),
                     Spawn(Text::new("No Delta Time"))
                 ),
                 "Updates once every render frame, with a fixed delta time value. Simulation speed is proportional to framerate."
             ),
             describe(
                 checkbox(
-                    observe(toggle_timestep(ActiveTimesteps::VARIABLE_DELTA)),
+                    (
+                        TimestepCheckbox(ActiveTimesteps::VARIABLE_DELTA),
+                        observe(toggle_timestep(ActiveTimesteps::VARIABLE_DELTA)),
+                    ),
                     Spawn(Text::new("Variable Delta Time"))
                 ),
                 "Updates once every render frame, with a dynamic delta time value. Unaffected by the configured Simulation Rate."
@@ -91,6 +245,7 @@ pub fn timesteps() -> impl Bundle {
                 checkbox(
                     (
                         Checked,
+                        TimestepCheckbox(ActiveTimesteps::SEMI_FIXED),
                         observe(toggle_timestep(ActiveTimesteps::SEMI_FIXED)),
                     ),
                     Spawn(Text::new("Semi-Fixed Timestep"))
@@ -99,7 +254,10 @@ pub fn timesteps() -> impl Bundle {
             ),
             describe(
                 checkbox(
-                    observe(toggle_timestep(ActiveTimesteps::FIXED)),
+                    (
+                        TimestepCheckbox(ActiveTimesteps::FIXED),
+                        observe(toggle_timestep(ActiveTimesteps::FIXED)),
+                    ),
                     Spawn(Text::new("Fixed Timestep"))
                 ),
                 "Updates zero or more times per frame, with a fixed delta time value. Causes visual stuttering."
@@ -126,6 +284,162 @@ pub fn timesteps() -> impl Bundle {
                 ),
                 "Extrapolate to a future value. Visual state will sometimes be temporarily incorrect."
             ),
+            describe(
+                checkbox(
+                    (
+                        PauseCheckbox,
+                        observe(
+                            |on: On<ValueChange<bool>>,
+                             mut pause: ResMut<Pause>,
+                             mut commands: Commands| {
+                                pause.paused = on.value;
+                                if on.value {
+                                    commands.entity(on.source).insert(Checked);
+                                } else {
+                                    commands.entity(on.source).remove::<Checked>();
+                                }
+                            }
+                        )
+                    ),
+                    Spawn(Text::new("Pause"))
+                ),
+                "Freezes every timestep, along with the update cadence graph and interpolation."
+            ),
+            describe(
+                button(
+                    ButtonProps::default(),
+                    observe(|_: On<Activate>, mut pause: ResMut<Pause>| {
+                        if pause.paused {
+                            pause.step_frame = true;
+                        }
+                    }),
+                    Spawn(Text::new("Step Frame"))
+                ),
+                "While paused, advance by exactly one render frame."
+            ),
+            describe(
+                (
+                    Node {
+                        column_gap: GAP_SIZE,
+                        ..default()
+                    },
+                    children![
+                        step_tick_button(Timestep::NoDelta),
+                        step_tick_button(Timestep::VariableDelta),
+                        step_tick_button(Timestep::SemiFixed),
+                        step_tick_button(Timestep::Fixed),
+                    ],
+                ),
+                "While paused, run exactly one simulation update of a single timestep, using the configured Simulation Rate as the delta time."
+            ),
         ],
     )
 }
+
+fn sync_simulation_rate_slider(
+    mut commands: Commands,
+    slider: Single<(Entity, &SliderValue), With<SimulationRateSlider>>,
+    simulation_delta: Res<SimulationDelta>,
+) {
+    // Round off the imprecision from converting back and forth between rate and delta
+    let rate = simulation_delta.0.as_secs_f32().recip().round();
+    sync_slider(&mut commands, *slider, rate);
+}
+
+fn sync_timestep_checkboxes(
+    mut commands: Commands,
+    checkboxes: Query<(Entity, Has<Checked>, &TimestepCheckbox)>,
+    active_timesteps: Res<ActiveTimesteps>,
+) {
+    for (entity, is_checked, timestep) in checkboxes.iter() {
+        sync_checked(
+            &mut commands,
+            entity,
+            is_checked,
+            active_timesteps.contains(timestep.0),
+        );
+    }
+}
+
+fn sync_interpolation_radios(
+    mut commands: Commands,
+    radios: Query<(Entity, Has<Checked>, &InterpolationMode)>,
+    mode: Res<InterpolationMode>,
+) {
+    for (entity, is_checked, &radio_mode) in radios.iter() {
+        sync_checked(&mut commands, entity, is_checked, radio_mode == *mode);
+    }
+}
+
+fn sync_pause_checkbox(
+    mut commands: Commands,
+    checkbox: Single<(Entity, Has<Checked>), With<PauseCheckbox>>,
+    pause: Res<Pause>,
+) {
+    let (entity, is_checked) = *checkbox;
+    sync_checked(&mut commands, entity, is_checked, pause.paused);
+}
+
+/// Virtual time changes every frame, so the last synced speed is kept to only sync when the speed itself changes
+fn sync_time_scale_slider(
+    mut commands: Commands,
+    slider: Single<(Entity, &SliderValue), With<TimeScaleSlider>>,
+    time: Res<Time<Virtual>>,
+    mut last_synced: Local<Option<f32>>,
+) {
+    let time_scale = time.relative_speed();
+    if *last_synced == Some(time_scale) {
+        return;
+    }
+    *last_synced = Some(time_scale);
+
+    sync_slider(&mut commands, *slider, time_scale);
+}
+
+/// Virtual time changes every frame, so the last synced max delta is kept to only sync when the max delta itself changes
+fn sync_max_delta_widgets(
+    mut commands: Commands,
+    slider: Single<(Entity, &SliderValue), With<MaxDeltaSlider>>,
+    checkbox: Single<(Entity, Has<Checked>), With<UnclampedCheckbox>>,
+    time: Res<Time<Virtual>>,
+    mut last_synced: Local<Option<Duration>>,
+) {
+    let max_delta = time.max_delta();
+    if *last_synced == Some(max_delta) {
+        return;
+    }
+    let was_unclamped = last_synced.map(|max_delta| max_delta == Duration::MAX);
+    *last_synced = Some(max_delta);
+
+    let unclamped = max_delta == Duration::MAX;
+    let (entity, is_checked) = *checkbox;
+    sync_checked(&mut commands, entity, is_checked, unclamped);
+
+    if was_unclamped != Some(unclamped) {
+        if unclamped {
+            commands.entity(slider.0).insert(InteractionDisabled);
+        } else {
+            commands.entity(slider.0).remove::<InteractionDisabled>();
+        }
+    }
+    // Keep the slider where it was, so that it can be restored when clamping is turned back on
+    if !unclamped {
+        sync_slider(&mut commands, *slider, max_delta.as_secs_f32() * 1000.0);
+    }
+}
+
+fn update_clamping_indicator(
+    mut indicator: Single<&mut BackgroundColor, With<ClampingIndicator>>,
+    mut text: Single<&mut Text, With<ClampingCountText>>,
+    clamping: Res<DeltaClamping>,
+) {
+    indicator.0 = if clamping.clamped {
+        palette::X_AXIS
+    } else {
+        palette::GRAY_3
+    };
+
+    if clamping.is_changed() {
+        text.0 = format!("Clamped frames: {}", clamping.count);
+    }
+}
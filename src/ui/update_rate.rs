@@ -8,8 +8,10 @@ use bevy::{
     ui_widgets::{RadioGroup, SliderValue, ValueChange, observe},
     winit::{UpdateMode, WinitSettings},
 };
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
 
-use crate::ui::{TabCorners, describe, tabs};
+use crate::ui::{TabCorners, describe, sync_checked, sync_slider, tabs};
 
 #[derive(Clone, Copy)]
 enum Focus {
@@ -18,12 +20,27 @@ enum Focus {
 }
 
 impl Focus {
+    fn from_is_focused(is_focused: bool) -> Self {
+        if is_focused {
+            Self::Focused
+        } else {
+            Self::Unfocused
+        }
+    }
+
     fn mode(self, settings: &mut CachedWinitSettings) -> &mut CachedUpdateMode {
         match self {
             Self::Focused => &mut settings.focused,
             Self::Unfocused => &mut settings.unfocused,
         }
     }
+
+    fn mode_ref(self, settings: &CachedWinitSettings) -> &CachedUpdateMode {
+        match self {
+            Self::Focused => &settings.focused,
+            Self::Unfocused => &settings.unfocused,
+        }
+    }
 }
 
 #[derive(Component)]
@@ -90,8 +107,8 @@ impl DefaultVariantChecked for (Unfocused, Reactive) {
     }
 }
 
-#[derive(Component, Clone, Copy)]
-enum UpdateModeVariant {
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize, ValueEnum)]
+pub enum UpdateModeVariant {
     Continuous,
     Reactive,
 }
@@ -107,10 +124,11 @@ impl UpdateModeVariant {
 
 /// `UpdateMode` is an enum that doesn't retain the reactive mode configuration when set to continuous,
 /// so we store that configuration here instead to make updating it easier
-#[derive(Resource)]
-struct CachedWinitSettings {
-    focused: CachedUpdateMode,
-    unfocused: CachedUpdateMode,
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CachedWinitSettings {
+    pub focused: CachedUpdateMode,
+    pub unfocused: CachedUpdateMode,
 }
 
 impl Default for CachedWinitSettings {
@@ -123,12 +141,13 @@ impl Default for CachedWinitSettings {
     }
 }
 
-struct CachedUpdateMode {
-    variant: UpdateModeVariant,
-    wait: Duration,
-    react_to_device_events: bool,
-    react_to_user_events: bool,
-    react_to_window_events: bool,
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedUpdateMode {
+    pub variant: UpdateModeVariant,
+    pub wait: Duration,
+    pub react_to_device_events: bool,
+    pub react_to_user_events: bool,
+    pub react_to_window_events: bool,
 }
 
 impl CachedUpdateMode {
@@ -139,6 +158,14 @@ impl CachedUpdateMode {
             ReactiveEvents::Window => &mut self.react_to_window_events,
         }
     }
+
+    fn events_ref(&self, events: ReactiveEvents) -> bool {
+        match events {
+            ReactiveEvents::Device => self.react_to_device_events,
+            ReactiveEvents::User => self.react_to_user_events,
+            ReactiveEvents::Window => self.react_to_window_events,
+        }
+    }
 }
 
 impl From<UpdateMode> for CachedUpdateMode {
@@ -210,12 +237,23 @@ fn toggle_reactive(
 #[derive(Component)]
 struct ReactiveConfigWidget;
 
+#[derive(Component)]
+struct UpdateFrequencySlider;
+
+#[derive(Component)]
+struct ReactiveEventsCheckbox(ReactiveEvents);
+
 #[derive(Component, Default)]
 struct UpdateModeTabs;
 
 pub fn plugin(app: &mut App) {
-    app.init_resource::<CachedWinitSettings>()
-        .add_systems(Update, update_winit_settings);
+    app.init_resource::<CachedWinitSettings>().add_systems(
+        Update,
+        (
+            update_winit_settings,
+            sync_widgets.run_if(resource_changed::<CachedWinitSettings>),
+        ),
+    );
 }
 
 pub fn update_rate() -> impl Bundle {
@@ -291,6 +329,7 @@ where
                     max: 1000.0
                 },
                 (
+                    UpdateFrequencySlider,
                     F::reactive_widget_disabled(),
                     observe(
                         move |on: On<ValueChange<f32>>,
@@ -308,6 +347,7 @@ where
                     (
                         Checked,
                         F::reactive_widget_disabled(),
+                        ReactiveEventsCheckbox(ReactiveEvents::Device),
                         observe(toggle_reactive(focus, ReactiveEvents::Device))
                     ),
                     Spawn(Text::new("Device Events"))
@@ -319,6 +359,7 @@ where
                     (
                         Checked,
                         F::reactive_widget_disabled(),
+                        ReactiveEventsCheckbox(ReactiveEvents::User),
                         observe(toggle_reactive(focus, ReactiveEvents::User))
                     ),
                     Spawn(Text::new("User Events"))
@@ -330,6 +371,7 @@ where
                     (
                         Checked,
                         F::reactive_widget_disabled(),
+                        ReactiveEventsCheckbox(ReactiveEvents::Window),
                         observe(toggle_reactive(focus, ReactiveEvents::Window))
                     ),
                     Spawn(Text::new("Window Events"))
@@ -348,3 +390,37 @@ fn update_winit_settings(mut winit: ResMut<WinitSettings>, cached: Res<CachedWin
     winit.focused_mode = (&cached.focused).into();
     winit.unfocused_mode = (&cached.unfocused).into();
 }
+
+fn sync_widgets(
+    mut commands: Commands,
+    radios: Query<(Entity, Has<Checked>, &UpdateModeVariant, Has<Focused>)>,
+    reactive_widgets: Query<(Entity, Has<Focused>), With<ReactiveConfigWidget>>,
+    sliders: Query<(Entity, &SliderValue, Has<Focused>), With<UpdateFrequencySlider>>,
+    checkboxes: Query<(Entity, Has<Checked>, &ReactiveEventsCheckbox, Has<Focused>)>,
+    settings: Res<CachedWinitSettings>,
+) {
+    for (entity, is_checked, &variant, is_focused) in radios.iter() {
+        let mode = Focus::from_is_focused(is_focused).mode_ref(&settings);
+        sync_checked(&mut commands, entity, is_checked, variant == mode.variant);
+    }
+
+    for (entity, is_focused) in reactive_widgets.iter() {
+        let mode = Focus::from_is_focused(is_focused).mode_ref(&settings);
+        mode.variant
+            .toggle_reactive_disabled(commands.entity(entity));
+    }
+
+    for (entity, value, is_focused) in sliders.iter() {
+        let mode = Focus::from_is_focused(is_focused).mode_ref(&settings);
+        sync_slider(
+            &mut commands,
+            (entity, value),
+            mode.wait.as_secs_f32().recip(),
+        );
+    }
+
+    for (entity, is_checked, events, is_focused) in checkboxes.iter() {
+        let mode = Focus::from_is_focused(is_focused).mode_ref(&settings);
+        sync_checked(&mut commands, entity, is_checked, mode.events_ref(events.0));
+    }
+}
@@ -13,6 +13,7 @@ use bevy::{
     platform::collections::HashMap,
     prelude::*,
     render::storage::ShaderStorageBuffer,
+    time::common_conditions::paused,
 };
 
 /// [`GlobalZIndex`] used to render the overlay.
@@ -29,6 +30,7 @@ const MAX_HISTORY_LENGTH: usize = 120;
 /// A plugin that adds an update cadence overlay to the Bevy application.
 ///
 /// Visualises how many times each frame the given schedule is ran.
+/// The graphs are frozen while [`Time<Virtual>`] is paused.
 ///
 /// By default, provides a graph for the FixedUpdate schedule.
 ///
@@ -40,6 +42,7 @@ pub struct UpdateCadencePlugin {
 
 impl UpdateCadencePlugin {
     /// No graphs for no schedules.
+    #[allow(dead_code)]
     pub fn new() -> Self {
         Self { configs: default() }
     }
@@ -51,6 +54,7 @@ impl UpdateCadencePlugin {
     }
 
     /// Add a graph with the given configuration.
+    #[allow(dead_code)]
     pub fn add_schedule_config(mut self, config: UpdateCadenceConfig) -> Self {
         self.configs.push(config);
         self
@@ -97,7 +101,8 @@ impl Plugin for UpdateCadencePlugin {
                 update_schedule_name,
             ),
         )
-        .add_systems(Last, flush_counts);
+        // Freeze the graphs while paused, so that they can be inspected
+        .add_systems(Last, flush_counts.run_if(not(paused)));
     }
 }
 
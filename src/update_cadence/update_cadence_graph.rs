@@ -7,6 +7,7 @@ use bevy::{
         storage::ShaderStorageBuffer,
     },
     shader::ShaderRef,
+    time::common_conditions::paused,
 };
 
 use super::{ScheduleUpdateCounts, UpdateCadenceConfig};
@@ -32,7 +33,7 @@ impl Plugin for UpdateCadenceGraphPlugin {
         }
 
         app.add_plugins(UiMaterialPlugin::<UpdateCadenceGraphMaterial>::default())
-            .add_systems(Update, update_frame_time_values);
+            .add_systems(Update, update_frame_time_values.run_if(not(paused)));
     }
 }
 